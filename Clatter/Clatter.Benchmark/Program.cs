@@ -23,13 +23,23 @@ namespace Clatter.Benchmark
             path = Path.Combine(path, "docs", "benchmark.md");
             string text = File.ReadAllText(path).Split("**RESULTS:**")[0].Trim() + "\n\n**RESULTS:**\n\n";
             double impact = ImpactBenchmark();
-            double scrape = ScrapeBenchmark();
+            double scrape = ScrapeBenchmark(InterpolationMode.linear, false, 1);
+            double cubicScrape = ScrapeBenchmark(InterpolationMode.cubic, false, 1);
+            double unfilteredScrape = ScrapeBenchmark(InterpolationMode.linear, true, 1);
+            double oversampledScrape = ScrapeBenchmark(InterpolationMode.linear, false, 4);
             double threadedTotal;
             double threadedAverage;
             ThreadedBenchmark(out threadedTotal, out threadedAverage);
+            double convolution = ConvolutionBenchmark(false);
+            double unrolledConvolution = ConvolutionBenchmark(true);
+            double fftConvolutionCost;
+            string fftConvolutionTable = FftConvolutionBenchmark(out fftConvolutionCost);
+            double modes = ModesBenchmark();
             string table = "| Benchmark | Time (seconds) |\n| --- | --- |\n| Impact | " + impact + " |\n| Scrape | " +
-                    scrape + " |\n| Threaded (total) | " + threadedTotal + " |\n| Threaded (average) | " +
-                    threadedAverage + " |"; 
+                    scrape + " |\n| Scrape (cubic) | " + cubicScrape + " |\n| Scrape (no median filter) | " + unfilteredScrape + " |\n| Scrape (4x oversampling) | " + oversampledScrape + " |\n| Threaded (total) | " + threadedTotal + " |\n| Threaded (average) | " +
+                    threadedAverage + " |\n| Convolution | " + convolution + " |\n| Convolution (unrolled) | " +
+                    unrolledConvolution + " |\n| Modes | " + modes + " |";
+            table += "\n\n**FFT convolution:**\n\n" + fftConvolutionTable + "\n\nMeasured `DoubleExtensions.fftConvolutionCost`: " + fftConvolutionCost;
             Console.WriteLine(table);
             File.WriteAllText(path, text + table);
         }
@@ -63,8 +73,11 @@ namespace Clatter.Benchmark
         }
 
 
-        private static double ScrapeBenchmark()
+        private static double ScrapeBenchmark(InterpolationMode interpolationMode, bool bypassMedianFilter, int saturationOversampling)
         {
+            Scrape.interpolationMode = interpolationMode;
+            Scrape.bypassMedianFilter = bypassMedianFilter;
+            Scrape.saturationOversampling = saturationOversampling;
             // Load the materials.
             ImpactMaterial primaryMaterial = ImpactMaterial.glass_1;
             ImpactMaterial secondaryMaterial = ImpactMaterial.stone_4;
@@ -87,6 +100,123 @@ namespace Clatter.Benchmark
                 scrape.GetAudio(1);
             }
             watch.Stop();
+            Scrape.interpolationMode = InterpolationMode.linear;
+            Scrape.bypassMedianFilter = false;
+            Scrape.saturationOversampling = 1;
+            return watch.Elapsed.TotalSeconds;
+        }
+
+
+        private static double ConvolutionBenchmark(bool unroll)
+        {
+            // Use direct convolution rather than FFT convolution.
+            int fftConvolutionKernelLength = DoubleExtensions.fftConvolutionKernelLength;
+            DoubleExtensions.fftConvolutionKernelLength = int.MaxValue;
+            DoubleExtensions.unrollConvolution = unroll;
+            Random rng = new Random(0);
+            double[] input = new double[Scrape.SAMPLES_LENGTH];
+            double[] kernel = new double[Scrape.SAMPLES_LENGTH];
+            for (int i = 0; i < input.Length; i++)
+            {
+                input[i] = rng.NextDouble() * 2 - 1;
+                kernel[i] = rng.NextDouble() * 2 - 1;
+            }
+            double[] result = new double[input.Length];
+            Stopwatch watch = new Stopwatch();
+            watch.Start();
+            for (int i = 0; i < 10; i++)
+            {
+                input.Convolve(kernel, input.Length, ref result);
+            }
+            watch.Stop();
+            DoubleExtensions.fftConvolutionKernelLength = fftConvolutionKernelLength;
+            DoubleExtensions.unrollConvolution = false;
+            return watch.Elapsed.TotalSeconds;
+        }
+
+
+        private static string FftConvolutionBenchmark(out double cost)
+        {
+            int[] inputLengths = { 4410, 44100, 176400 };
+            int[] kernelLengths = { 32, 128, 512, 2048, 4410 };
+            // Use direct convolution in ConvolveFull().
+            int fftConvolutionKernelLength = DoubleExtensions.fftConvolutionKernelLength;
+            DoubleExtensions.fftConvolutionKernelLength = int.MaxValue;
+            Random rng = new Random(0);
+            Stopwatch watch = new Stopwatch();
+            List<double> costs = new List<double>();
+            string table = "| Input length | Kernel length | Direct (seconds) | FFT (seconds) |\n| --- | --- | --- | --- |";
+            foreach (int inputLength in inputLengths)
+            {
+                double[] input = new double[inputLength];
+                for (int i = 0; i < inputLength; i++)
+                {
+                    input[i] = rng.NextDouble() * 2 - 1;
+                }
+                foreach (int kernelLength in kernelLengths)
+                {
+                    double[] kernel = new double[kernelLength];
+                    for (int i = 0; i < kernelLength; i++)
+                    {
+                        kernel[i] = rng.NextDouble() * 2 - 1;
+                    }
+                    int length = inputLength + kernelLength - 1;
+                    double[] result = new double[length];
+                    // Repeat short convolutions so that they can be timed accurately.
+                    int count = Math.Max(1, 20000000 / (inputLength * kernelLength));
+                    // Warm up both methods.
+                    input.ConvolveFull(inputLength, kernel, kernelLength, ref result);
+                    input.ConvolveFft(inputLength, kernel, kernelLength, length, ref result);
+                    watch.Restart();
+                    for (int i = 0; i < count; i++)
+                    {
+                        input.ConvolveFull(inputLength, kernel, kernelLength, ref result);
+                    }
+                    watch.Stop();
+                    double direct = watch.Elapsed.TotalSeconds / count;
+                    watch.Restart();
+                    for (int i = 0; i < count; i++)
+                    {
+                        input.ConvolveFft(inputLength, kernel, kernelLength, length, ref result);
+                    }
+                    watch.Stop();
+                    double fft = watch.Elapsed.TotalSeconds / count;
+                    table += "\n| " + inputLength + " | " + kernelLength + " | " + direct + " | " + fft + " |";
+                    // The time per unit of FFT work (n * log2(n)) relative to the time per direct multiply-add.
+                    double n = Fft.GetSize(length);
+                    costs.Add((fft / (n * Math.Log(n, 2))) / (direct / ((double)inputLength * kernelLength)));
+                }
+            }
+            DoubleExtensions.fftConvolutionKernelLength = fftConvolutionKernelLength;
+            costs.Sort();
+            cost = costs[costs.Count / 2];
+            return table;
+        }
+
+
+        private static double ModesBenchmark()
+        {
+            // Load the material.
+            ImpactMaterial material = ImpactMaterial.glass_1;
+            ImpactMaterialData.Load(material);
+            ImpactMaterialData materialData = ImpactMaterialData.impactMaterials[material];
+            double[] frequencies = new double[materialData.cf.Length];
+            double[] powers = new double[materialData.op.Length];
+            double[] decayTimes = new double[materialData.rt.Length];
+            for (int i = 0; i < frequencies.Length; i++)
+            {
+                frequencies[i] = materialData.cf[i];
+                powers[i] = materialData.op[i];
+                decayTimes[i] = materialData.rt[i] * 1e3;
+            }
+            double[] synthSound = new double[Globals.DEFAULT_SAMPLES_LENGTH];
+            Stopwatch watch = new Stopwatch();
+            watch.Start();
+            for (int i = 0; i < 100; i++)
+            {
+                Modes.Sum(frequencies, powers, decayTimes, 0.2, ref synthSound);
+            }
+            watch.Stop();
             return watch.Elapsed.TotalSeconds;
         }
 
@@ -1,4 +1,5 @@
-﻿using Clatter.Core;
+﻿using System.Text.Json;
+using Clatter.Core;
 
 
 namespace Clatter.CommandLine
@@ -30,6 +31,7 @@ namespace Clatter.CommandLine
             {"--duration [FLOAT]", "If --type is scrape, this sets the duration of the scrape audio."},
             {"--simulation_amp [FLOAT]", "The overall amp (0-1)."},
             {"--type [STRING]", "The type of audio event. Options: impact, scrape"},
+            {"--modes [STRING]", "OPTIONAL. The path to a JSON file of impact modes data. If included, impact audio is synthesized from the modes (see: Impact.Synthesize) and the material, speed, and type arguments are ignored. The JSON object must have these keys: frequencies, powers, decay_times (arrays of floats), resonance, contact_time, amp."},
             {"--path [STRING]", "OPTIONAL. The path to a .wav file. If not included, audio will be written to stdout."},
             {"--allow_distortion", "OPTIONAL. If included, don't clamp impact amp values to 0.99. See: Impact.preventDistortion"},
             {"--unclamp_contact_time", "OPTIONAL. If included, don't clamp impact contact times to plausible values. See: Impact.clampContactTime"},
@@ -65,6 +67,13 @@ namespace Clatter.CommandLine
             {
                 Globals.framerateD = Globals.framerate;
             }
+            // Synthesize audio from modes data.
+            string modesPath = "";
+            if (ArgumentParser.TryGetStringValue(args, "modes", ref modesPath))
+            {
+                WriteAudio(args, GetModesAudio(modesPath));
+                return;
+            }
             // Get the primary object's data.
             ImpactMaterial primaryImpactMaterial;
             double primaryAmp;
@@ -109,11 +118,38 @@ namespace Clatter.CommandLine
             // Get the speed.
             double speed = ArgumentParser.GetDoubleValue(args, "speed");
             // Generate audio.
-            byte[] audio = ExternalEntryPoint.GetAudio((byte)primaryImpactMaterial, primaryAmp, primaryResonance, primaryMass, 
-                (byte)secondaryImpactMaterial, secondaryAmp, secondaryResonance, secondaryMass, speed, 
-                (byte)audioEventType, (byte)scrapeMaterial, scrapeDuration, false, 0, 
-                AudioEvent.simulationAmp,  Scrape.maxSpeed, Impact.preventDistortion, Impact.clampContactTime,
-                Globals.framerate);
+            ExternalAudioParameters parameters = new ExternalAudioParameters
+            {
+                primaryImpactMaterial = (byte)primaryImpactMaterial,
+                primaryAmp = primaryAmp,
+                primaryResonance = primaryResonance,
+                primaryMass = primaryMass,
+                secondaryImpactMaterial = (byte)secondaryImpactMaterial,
+                secondaryAmp = secondaryAmp,
+                secondaryResonance = secondaryResonance,
+                secondaryMass = secondaryMass,
+                speed = speed,
+                audioEventType = (byte)audioEventType,
+                scrapeMaterial = (byte)scrapeMaterial,
+                duration = scrapeDuration,
+                simulationAmp = AudioEvent.simulationAmp,
+                scrapeMaxSpeed = Scrape.maxSpeed,
+                preventDistortion = Impact.preventDistortion,
+                clampContactTime = Impact.clampContactTime,
+                framerate = Globals.framerate
+            };
+            byte[] audio = ExternalEntryPoint.GetAudio(parameters);
+            WriteAudio(args, audio);
+        }
+
+
+        /// <summary>
+        /// Write audio to a .wav file or to standard output.
+        /// </summary>
+        /// <param name="args">The args.</param>
+        /// <param name="audio">The int16 audio data.</param>
+        private static void WriteAudio(string[] args, byte[] audio)
+        {
             // Write a wav file.
             string path = "";
             if (ArgumentParser.TryGetStringValue(args, "path", ref path))
@@ -133,6 +169,43 @@ namespace Clatter.CommandLine
         }
 
 
+        /// <summary>
+        /// Synthesize impact audio from a JSON file of modes data. Returns the int16 audio data.
+        /// </summary>
+        /// <param name="path">The path to the JSON file.</param>
+        private static byte[] GetModesAudio(string path)
+        {
+            using (JsonDocument document = JsonDocument.Parse(File.ReadAllText(path)))
+            {
+                JsonElement root = document.RootElement;
+                double[] frequencies = GetDoubles(root, "frequencies");
+                double[] powers = GetDoubles(root, "powers");
+                double[] decayTimes = GetDoubles(root, "decay_times");
+                double[] samples = new double[Globals.DEFAULT_SAMPLES_LENGTH];
+                int length = Impact.Synthesize(frequencies, powers, decayTimes, root.GetProperty("resonance").GetDouble(),
+                    root.GetProperty("contact_time").GetDouble(), root.GetProperty("amp").GetDouble(), ref samples);
+                return samples.ToInt16Bytes(length);
+            }
+        }
+
+
+        /// <summary>
+        /// Returns an array of doubles from a JSON object.
+        /// </summary>
+        /// <param name="root">The JSON object.</param>
+        /// <param name="key">The key of the array.</param>
+        private static double[] GetDoubles(JsonElement root, string key)
+        {
+            JsonElement array = root.GetProperty(key);
+            double[] values = new double[array.GetArrayLength()];
+            for (int i = 0; i < values.Length; i++)
+            {
+                values[i] = array[i].GetDouble();
+            }
+            return values;
+        }
+
+
         /// <summary>
         /// Get Clatter object data.
         /// </summary>
@@ -0,0 +1,316 @@
+﻿using System;
+
+
+namespace Clatter.Core
+{
+    /// <summary>
+    /// Measure audio samples. This can be useful for automatic gain staging.
+    /// </summary>
+    public static class Analysis
+    {
+        /// <summary>
+        /// The level in dBFS that is returned for silent or empty audio data, instead of negative infinity.
+        /// </summary>
+        public const double SILENCE_DB = -120;
+        /// <summary>
+        /// The FNV-1a 64-bit offset basis. See: Fingerprint().
+        /// </summary>
+        private const ulong FNV_OFFSET_BASIS = 14695981039346656037;
+        /// <summary>
+        /// The FNV-1a 64-bit prime. See: Fingerprint().
+        /// </summary>
+        private const ulong FNV_PRIME = 1099511628211;
+
+
+        /// <summary>
+        /// Returns the root mean square of the samples. If the audio data is empty, this returns 0.
+        /// </summary>
+        /// <param name="samples">The audio samples.</param>
+        /// <param name="length">The length of the audio data (can be less than the true length of the array).</param>
+        public static double Rms(double[] samples, int length)
+        {
+            if (length <= 0)
+            {
+                return 0;
+            }
+            double sum = 0;
+            for (int i = 0; i < length; i++)
+            {
+                sum += samples[i] * samples[i];
+            }
+            return Math.Sqrt(sum / length);
+        }
+
+
+        /// <summary>
+        /// Returns the absolute peak of the samples. If the audio data is empty, this returns 0.
+        /// </summary>
+        /// <param name="samples">The audio samples.</param>
+        /// <param name="length">The length of the audio data (can be less than the true length of the array).</param>
+        public static double Peak(double[] samples, int length)
+        {
+            double peak = 0;
+            double abs;
+            for (int i = 0; i < length; i++)
+            {
+                abs = Math.Abs(samples[i]);
+                if (abs > peak)
+                {
+                    peak = abs;
+                }
+            }
+            return peak;
+        }
+
+
+        /// <summary>
+        /// Returns the root mean square of the samples in dBFS. If the audio data is silent or empty, this returns SILENCE_DB.
+        /// </summary>
+        /// <param name="samples">The audio samples.</param>
+        /// <param name="length">The length of the audio data (can be less than the true length of the array).</param>
+        public static double RmsDbfs(double[] samples, int length)
+        {
+            return ToDbfs(Rms(samples, length));
+        }
+
+
+        /// <summary>
+        /// Returns the absolute peak of the samples in dBFS. If the audio data is silent or empty, this returns SILENCE_DB.
+        /// </summary>
+        /// <param name="samples">The audio samples.</param>
+        /// <param name="length">The length of the audio data (can be less than the true length of the array).</param>
+        public static double PeakDbfs(double[] samples, int length)
+        {
+            return ToDbfs(Peak(samples, length));
+        }
+
+
+        /// <summary>
+        /// Returns the index of the first sample whose absolute value is at least threshold times the absolute peak. When audio is convolved with an impulse response, the onset of the output is delayed by approximately this many samples, so this can be used to compensate for the delay, for example by triggering sounds earlier. If the audio data is silent or empty, this returns 0.
+        /// </summary>
+        /// <param name="samples">The audio samples, e.g. an impulse response.</param>
+        /// <param name="length">The length of the audio data (can be less than the true length of the array).</param>
+        /// <param name="threshold">The threshold as a fraction of the absolute peak (0 to 1), e.g. 0.1.</param>
+        public static int GetOnsetDelay(double[] samples, int length, double threshold)
+        {
+            double minimum = Peak(samples, length) * threshold;
+            if (!(minimum > 0))
+            {
+                return 0;
+            }
+            for (int i = 0; i < length; i++)
+            {
+                if (Math.Abs(samples[i]) >= minimum)
+                {
+                    return i;
+                }
+            }
+            return 0;
+        }
+
+
+        /// <summary>
+        /// Returns the spectral centroid of the samples in Hz: the mean frequency of the magnitude spectrum, weighted by magnitude. Brighter sounds (e.g. metal) have a higher spectral centroid than duller sounds (e.g. wood). If the audio data is silent or empty, this returns 0.
+        /// </summary>
+        /// <param name="samples">The audio samples.</param>
+        /// <param name="length">The length of the audio data (can be less than the true length of the array).</param>
+        public static double SpectralCentroid(double[] samples, int length)
+        {
+            if (length <= 0)
+            {
+                return 0;
+            }
+            int n = Fft.GetSize(length);
+            double[] real = new double[n];
+            double[] imaginary = new double[n];
+            Buffer.BlockCopy(samples, 0, real, 0, length * 8);
+            Fft.Transform(real, imaginary, n, false);
+            double weightedSum = 0;
+            double magnitudeSum = 0;
+            double magnitude;
+            // Only the first half of the spectrum is needed because the input is real.
+            for (int i = 0; i <= n / 2; i++)
+            {
+                magnitude = Math.Sqrt(real[i] * real[i] + imaginary[i] * imaginary[i]);
+                weightedSum += magnitude * i * Globals.framerateD / n;
+                magnitudeSum += magnitude;
+            }
+            if (magnitudeSum <= 0)
+            {
+                return 0;
+            }
+            return weightedSum / magnitudeSum;
+        }
+
+
+        /// <summary>
+        /// Returns an estimate of the fundamental frequency of the samples in Hz, using autocorrelation: the fundamental period is the lag within the frequency range at which the samples are most similar to themselves. This can be used to check that a synthesized impact has the expected pitch. If the audio data is silent or empty, this returns 0.
+        /// </summary>
+        /// <param name="samples">The audio samples.</param>
+        /// <param name="length">The length of the audio data (can be less than the true length of the array).</param>
+        /// <param name="minFrequency">The minimum frequency in Hz.</param>
+        /// <param name="maxFrequency">The maximum frequency in Hz. This must be greater than minFrequency.</param>
+        public static double EstimateFundamental(double[] samples, int length, double minFrequency, double maxFrequency)
+        {
+            if (!(minFrequency > 0) || !(maxFrequency > minFrequency))
+            {
+                throw new Exception("Invalid frequency range: " + minFrequency + ", " + maxFrequency);
+            }
+            if (length <= 0)
+            {
+                return 0;
+            }
+            int minLag = Math.Max((int)Math.Floor(Globals.framerateD / maxFrequency), 1);
+            int maxLag = Math.Min((int)Math.Ceiling(Globals.framerateD / minFrequency), length - 2);
+            if (minLag > maxLag)
+            {
+                return 0;
+            }
+            // Get the autocorrelation via the FFT. The data is zero-padded so that the autocorrelation isn't circular.
+            int n = Fft.GetSize(length * 2);
+            double[] real = new double[n];
+            double[] imaginary = new double[n];
+            Buffer.BlockCopy(samples, 0, real, 0, length * 8);
+            Fft.Transform(real, imaginary, n, false);
+            for (int i = 0; i < n; i++)
+            {
+                real[i] = real[i] * real[i] + imaginary[i] * imaginary[i];
+                imaginary[i] = 0;
+            }
+            Fft.Transform(real, imaginary, n, true);
+            if (!(real[0] > 0))
+            {
+                return 0;
+            }
+            // Find the lag with the highest autocorrelation.
+            int bestLag = minLag;
+            for (int i = minLag + 1; i <= maxLag; i++)
+            {
+                if (real[i] > real[bestLag])
+                {
+                    bestLag = i;
+                }
+            }
+            if (!(real[bestLag] > 0))
+            {
+                return 0;
+            }
+            // Refine the lag with parabolic interpolation.
+            double lag = bestLag;
+            double previous = real[bestLag - 1];
+            double next = real[bestLag + 1];
+            double denominator = previous - 2 * real[bestLag] + next;
+            if (denominator < 0)
+            {
+                lag += 0.5 * (previous - next) / denominator;
+            }
+            return Globals.framerateD / lag;
+        }
+
+
+        /// <summary>
+        /// Returns a stable 64-bit hash of the samples. This can be used to detect unintended changes to synthesized audio, for example in regression tests, without storing the audio itself.
+        ///
+        /// Each sample is clamped to be between -1 and 1, multiplied by 2^(bits - 1), and rounded to an integer. The integers and the length are hashed with FNV-1a. Quantizing the samples means that tiny floating-point differences between platforms usually don't change the hash; a larger number of bits is more sensitive. NaN is quantized to a value that no other sample can have.
+        ///
+        /// Source: http://www.isthe.com/chongo/tech/comp/fnv/index.html
+        /// </summary>
+        /// <param name="samples">The audio samples.</param>
+        /// <param name="length">The length of the audio data (can be less than the true length of the array).</param>
+        /// <param name="bits">The number of bits of the quantized samples (1 to 31).</param>
+        public static ulong Fingerprint(double[] samples, int length, int bits = 16)
+        {
+            if (bits < 1 || bits > 31)
+            {
+                throw new Exception("Invalid number of bits: " + bits);
+            }
+            double scale = 1L << (bits - 1);
+            ulong hash = FNV_OFFSET_BASIS;
+            hash = HashInt(hash, length);
+            int q;
+            for (int i = 0; i < length; i++)
+            {
+                q = double.IsNaN(samples[i]) ? int.MinValue : (int)Math.Round(samples[i].Clamp(-1, 1) * scale);
+                hash = HashInt(hash, q);
+            }
+            return hash;
+        }
+
+
+        /// <summary>
+        /// Returns the median of the samples. If there is an even number of samples, this returns the mean of the two middle values. The samples aren't modified.
+        ///
+        /// The samples are ordered with Array.Sort(), which places NaN before all other values (including negative infinity). This means that the ordering is deterministic even if there are NaN values, but the median will be NaN if at least half of the samples are NaN.
+        /// </summary>
+        /// <param name="samples">The audio samples.</param>
+        /// <param name="length">The length of the audio data (can be less than the true length of the array). This must be greater than 0.</param>
+        public static double Median(double[] samples, int length)
+        {
+            return Percentile(samples, length, 50);
+        }
+
+
+        /// <summary>
+        /// Returns the pth percentile of the samples. If the percentile falls between two samples, this linearly interpolates between them. The samples aren't modified.
+        ///
+        /// The samples are ordered with Array.Sort(), which places NaN before all other values (including negative infinity). This means that the ordering is deterministic even if there are NaN values, but low percentiles will be NaN if there are NaN values.
+        /// </summary>
+        /// <param name="samples">The audio samples.</param>
+        /// <param name="length">The length of the audio data (can be less than the true length of the array). This must be greater than 0.</param>
+        /// <param name="p">The percentile (0 to 100).</param>
+        public static double Percentile(double[] samples, int length, double p)
+        {
+            if (length <= 0)
+            {
+                throw new Exception("Can't get the percentile of empty data.");
+            }
+            if (!(p >= 0 && p <= 100))
+            {
+                throw new Exception("Invalid percentile: " + p);
+            }
+            // Copy the samples so that the input isn't modified.
+            double[] sorted = new double[length];
+            Buffer.BlockCopy(samples, 0, sorted, 0, length * 8);
+            Array.Sort(sorted);
+            double rank = p / 100 * (length - 1);
+            int lower = (int)Math.Floor(rank);
+            int upper = (int)Math.Ceiling(rank);
+            if (lower == upper)
+            {
+                return sorted[lower];
+            }
+            return sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower);
+        }
+
+
+        /// <summary>
+        /// Returns a hash with the four bytes of an integer added to it, in little-endian order. See: Fingerprint().
+        /// </summary>
+        /// <param name="hash">The hash.</param>
+        /// <param name="value">The integer.</param>
+        private static ulong HashInt(ulong hash, int value)
+        {
+            uint u = (uint)value;
+            for (int i = 0; i < 4; i++)
+            {
+                hash ^= (u >> (i * 8)) & 0xff;
+                hash *= FNV_PRIME;
+            }
+            return hash;
+        }
+
+
+        /// <summary>
+        /// Returns a linear amplitude converted to dBFS, clamped to be at least SILENCE_DB.
+        /// </summary>
+        /// <param name="amplitude">The linear amplitude.</param>
+        private static double ToDbfs(double amplitude)
+        {
+            if (amplitude <= 0)
+            {
+                return SILENCE_DB;
+            }
+            return Math.Max(20 * Math.Log10(amplitude), SILENCE_DB);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,143 @@
+﻿using System;
+
+
+namespace Clatter.Core
+{
+    /// <summary>
+    /// A second-order IIR filter. Use this to post-process audio, for example to remove harsh high frequencies from scrape audio.
+    ///
+    /// The filter coefficients are calculated using Robert Bristow-Johnson's Audio EQ Cookbook. Source: https://www.w3.org/TR/audio-eq-cookbook/
+    /// </summary>
+    public class Biquad
+    {
+        /// <summary>
+        /// The b0 coefficient, normalized by a0.
+        /// </summary>
+        private readonly double b0;
+        /// <summary>
+        /// The b1 coefficient, normalized by a0.
+        /// </summary>
+        private readonly double b1;
+        /// <summary>
+        /// The b2 coefficient, normalized by a0.
+        /// </summary>
+        private readonly double b2;
+        /// <summary>
+        /// The a1 coefficient, normalized by a0.
+        /// </summary>
+        private readonly double a1;
+        /// <summary>
+        /// The a2 coefficient, normalized by a0.
+        /// </summary>
+        private readonly double a2;
+        /// <summary>
+        /// The first state sample.
+        /// </summary>
+        private double z1;
+        /// <summary>
+        /// The second state sample.
+        /// </summary>
+        private double z2;
+
+
+        /// <summary>
+        /// (constructor)
+        /// </summary>
+        /// <param name="b0">The b0 coefficient.</param>
+        /// <param name="b1">The b1 coefficient.</param>
+        /// <param name="b2">The b2 coefficient.</param>
+        /// <param name="a0">The a0 coefficient. All of the other coefficients are normalized by this value.</param>
+        /// <param name="a1">The a1 coefficient.</param>
+        /// <param name="a2">The a2 coefficient.</param>
+        public Biquad(double b0, double b1, double b2, double a0, double a1, double a2)
+        {
+            this.b0 = b0 / a0;
+            this.b1 = b1 / a0;
+            this.b2 = b2 / a0;
+            this.a1 = a1 / a0;
+            this.a2 = a2 / a0;
+        }
+
+
+        /// <summary>
+        /// Returns a low-pass filter.
+        /// </summary>
+        /// <param name="cutoff">The cutoff frequency in Hz. This should be less than half of Globals.framerate.</param>
+        /// <param name="q">The Q factor. 0.7071 gives a maximally flat (Butterworth) response.</param>
+        public static Biquad LowPass(double cutoff, double q)
+        {
+            double w0 = 2 * Math.PI * cutoff / Globals.framerateD;
+            double cos = Math.Cos(w0);
+            double alpha = Math.Sin(w0) / (2 * q);
+            return new Biquad((1 - cos) / 2, 1 - cos, (1 - cos) / 2, 1 + alpha, -2 * cos, 1 - alpha);
+        }
+
+
+        /// <summary>
+        /// Returns a high-pass filter.
+        /// </summary>
+        /// <param name="cutoff">The cutoff frequency in Hz. This should be less than half of Globals.framerate.</param>
+        /// <param name="q">The Q factor. 0.7071 gives a maximally flat (Butterworth) response.</param>
+        public static Biquad HighPass(double cutoff, double q)
+        {
+            double w0 = 2 * Math.PI * cutoff / Globals.framerateD;
+            double cos = Math.Cos(w0);
+            double alpha = Math.Sin(w0) / (2 * q);
+            return new Biquad((1 + cos) / 2, -(1 + cos), (1 + cos) / 2, 1 + alpha, -2 * cos, 1 - alpha);
+        }
+
+
+        /// <summary>
+        /// Returns a peaking EQ filter, which boosts or cuts a band of frequencies around a center frequency. This can be used to emphasize or reduce frequencies that are characteristic of a material. The gain at the center frequency is gain dB, and the gain far from the center frequency is 0 dB.
+        /// </summary>
+        /// <param name="center">The center frequency in Hz. This should be less than half of Globals.framerate.</param>
+        /// <param name="q">The Q factor. Higher values affect a narrower band.</param>
+        /// <param name="gain">The gain at the center frequency in dB. Positive values boost the band and negative values cut it.</param>
+        public static Biquad Peaking(double center, double q, double gain)
+        {
+            double w0 = 2 * Math.PI * center / Globals.framerateD;
+            double cos = Math.Cos(w0);
+            double alpha = Math.Sin(w0) / (2 * q);
+            double a = Math.Pow(10, gain / 40);
+            return new Biquad(1 + alpha * a, -2 * cos, 1 - alpha * a, 1 + alpha / a, -2 * cos, 1 - alpha / a);
+        }
+
+
+        /// <summary>
+        /// Filter a single sample.
+        /// </summary>
+        /// <param name="sample">The sample.</param>
+        public double ProcessSample(double sample)
+        {
+            // Transposed direct form II.
+            double y = b0 * sample + z1;
+            z1 = b1 * sample - a1 * y + z2;
+            z2 = b2 * sample - a2 * y;
+            return y;
+        }
+
+
+        /// <summary>
+        /// Filter audio samples in-place.
+        /// </summary>
+        /// <param name="samples">The audio samples. This array will be modified.</param>
+        /// <param name="length">The length of the audio data.</param>
+        public void Process(double[] samples, int length)
+        {
+            for (int i = 0; i < length; i++)
+            {
+                samples[i] = ProcessSample(samples[i]);
+            }
+        }
+
+
+        /// <summary>
+        /// Clear the filter's state.
+        /// </summary>
+        public void Reset()
+        {
+            z1 = 0;
+            z2 = 0;
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,92 @@
+﻿using System;
+
+
+namespace Clatter.Core
+{
+    /// <summary>
+    /// A 4th-order Linkwitz-Riley crossover that splits audio into a low band and a high band. This is useful for multi-band processing, for example limiting only the low frequencies of heavy impacts.
+    ///
+    /// Each band is two cascaded Butterworth Biquad filters. The sum of the low band and the high band has the same magnitude as the input at every frequency, but the phase is shifted around the cutoff frequency (the sum is an allpass-filtered copy of the input).
+    ///
+    /// Source: https://en.wikipedia.org/wiki/Linkwitz%E2%80%93Riley_filter
+    /// </summary>
+    public class Crossover
+    {
+        /// <summary>
+        /// The Q factor of a Butterworth Biquad.
+        /// </summary>
+        private const double BUTTERWORTH_Q = 0.7071067811865476;
+
+
+        /// <summary>
+        /// The first low-pass filter.
+        /// </summary>
+        private readonly Biquad lowPass0;
+        /// <summary>
+        /// The second low-pass filter.
+        /// </summary>
+        private readonly Biquad lowPass1;
+        /// <summary>
+        /// The first high-pass filter.
+        /// </summary>
+        private readonly Biquad highPass0;
+        /// <summary>
+        /// The second high-pass filter.
+        /// </summary>
+        private readonly Biquad highPass1;
+
+
+        /// <summary>
+        /// (constructor)
+        /// </summary>
+        /// <param name="cutoff">The cutoff frequency in Hz. This should be less than half of Globals.framerate.</param>
+        public Crossover(double cutoff)
+        {
+            if (!(cutoff > 0 && cutoff < Globals.framerateD / 2))
+            {
+                throw new Exception("Invalid crossover cutoff frequency: " + cutoff);
+            }
+            lowPass0 = Biquad.LowPass(cutoff, BUTTERWORTH_Q);
+            lowPass1 = Biquad.LowPass(cutoff, BUTTERWORTH_Q);
+            highPass0 = Biquad.HighPass(cutoff, BUTTERWORTH_Q);
+            highPass1 = Biquad.HighPass(cutoff, BUTTERWORTH_Q);
+        }
+
+
+        /// <summary>
+        /// Split audio samples into a low band and a high band.
+        /// </summary>
+        /// <param name="samples">The audio samples. This array won't be modified.</param>
+        /// <param name="length">The length of the audio data.</param>
+        /// <param name="low">The low band. This will be resized if needed.</param>
+        /// <param name="high">The high band. This will be resized if needed.</param>
+        public void Process(double[] samples, int length, ref double[] low, ref double[] high)
+        {
+            if (low.Length < length)
+            {
+                Array.Resize(ref low, length);
+            }
+            if (high.Length < length)
+            {
+                Array.Resize(ref high, length);
+            }
+            for (int i = 0; i < length; i++)
+            {
+                low[i] = lowPass1.ProcessSample(lowPass0.ProcessSample(samples[i]));
+                high[i] = highPass1.ProcessSample(highPass0.ProcessSample(samples[i]));
+            }
+        }
+
+
+        /// <summary>
+        /// Clear the crossover's state.
+        /// </summary>
+        public void Reset()
+        {
+            lowPass0.Reset();
+            lowPass1.Reset();
+            highPass0.Reset();
+            highPass1.Reset();
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,75 @@
+﻿namespace Clatter.Core
+{
+    /// <summary>
+    /// A one-pole high-pass filter that removes DC offset from audio. This can prevent audible thumps and wasted headroom in scrape audio (see: Scrape.removeDcOffset).
+    ///
+    /// Source: https://ccrma.stanford.edu/~jos/filters/DC_Blocker.html
+    /// </summary>
+    public class DcBlocker
+    {
+        /// <summary>
+        /// The default pole coefficient.
+        /// </summary>
+        public const double DEFAULT_R = 0.995;
+
+
+        /// <summary>
+        /// The pole coefficient.
+        /// </summary>
+        private readonly double r;
+        /// <summary>
+        /// The previous input sample.
+        /// </summary>
+        private double previousInput;
+        /// <summary>
+        /// The previous output sample.
+        /// </summary>
+        private double previousOutput;
+
+
+        /// <summary>
+        /// (constructor)
+        /// </summary>
+        /// <param name="r">The pole coefficient (0 to 1). Values closer to 1 have a lower cutoff frequency, but take longer to remove the offset.</param>
+        public DcBlocker(double r = DEFAULT_R)
+        {
+            this.r = r;
+        }
+
+
+        /// <summary>
+        /// Filter a single sample.
+        /// </summary>
+        /// <param name="sample">The sample.</param>
+        public double ProcessSample(double sample)
+        {
+            previousOutput = sample - previousInput + r * previousOutput;
+            previousInput = sample;
+            return previousOutput;
+        }
+
+
+        /// <summary>
+        /// Filter audio samples in-place.
+        /// </summary>
+        /// <param name="samples">The audio samples. This array will be modified.</param>
+        /// <param name="length">The length of the audio data.</param>
+        public void Process(double[] samples, int length)
+        {
+            for (int i = 0; i < length; i++)
+            {
+                samples[i] = ProcessSample(samples[i]);
+            }
+        }
+
+
+        /// <summary>
+        /// Clear the filter's state.
+        /// </summary>
+        public void Reset()
+        {
+            previousInput = 0;
+            previousOutput = 0;
+        }
+    }
+}
\ No newline at end of file
@@ -12,6 +12,51 @@ namespace Clatter.Core
         /// Conversion factor for float to short.
         /// </summary>
         private const int FLOAT_TO_SHORT = 32767;
+        /// <summary>
+        /// ConvolveFft() caches its buffers per thread if the FFT size is less than or equal to this. Larger buffers, for example for a long impulse response, are allocated per call so that they aren't kept alive by each thread.
+        /// </summary>
+        private const int MAX_CACHED_FFT_SIZE = 65536;
+
+
+        /// <summary>
+        /// Convolve() and ConvolveFull() never use ConvolveFft() if the kernel is shorter than this. Very short kernels, such as an impact's contact force, are always faster to convolve directly.
+        /// </summary>
+        public static int fftConvolutionKernelLength = 128;
+        /// <summary>
+        /// The cost of FFT convolution relative to direct convolution. If the kernel is at least fftConvolutionKernelLength long, Convolve() and ConvolveFull() use ConvolveFft() if the number of direct multiply-adds (the input length times the kernel length) is greater than fftConvolutionCost * n * log2(n), where n is the FFT size. The decision therefore depends on both the input length and the kernel length: a long kernel convolved with a very long input, such as an impulse response, can still be faster to convolve directly.
+        ///
+        /// Clatter.Benchmark measures this factor for the current machine (see: docs/benchmark.md).
+        /// </summary>
+        public static double fftConvolutionCost = 8;
+        /// <summary>
+        /// If true, Convolve() processes four kernel taps per iteration using independent accumulators. This allows the CPU to run the multiply-adds in parallel and is typically faster for long kernels, but because the sum is reassociated, the result can differ from the default path by floating-point rounding error (roughly 1e-12 or less).
+        /// </summary>
+        public static bool unrollConvolution;
+        /// <summary>
+        /// Cached FFT real values for the input array.
+        /// </summary>
+        [ThreadStatic]
+        private static double[] cachedFftInputReal;
+        /// <summary>
+        /// Cached FFT imaginary values for the input array.
+        /// </summary>
+        [ThreadStatic]
+        private static double[] cachedFftInputImaginary;
+        /// <summary>
+        /// Cached FFT real values for the kernel.
+        /// </summary>
+        [ThreadStatic]
+        private static double[] cachedFftKernelReal;
+        /// <summary>
+        /// Cached FFT imaginary values for the kernel.
+        /// </summary>
+        [ThreadStatic]
+        private static double[] cachedFftKernelImaginary;
+        /// <summary>
+        /// The cached full convolution used by ConvolveWithTail().
+        /// </summary>
+        [ThreadStatic]
+        private static double[] fullConvolution;
         
         
         /// <summary>
@@ -37,8 +82,109 @@ namespace Clatter.Core
         }
 
 
+        /// <summary>
+        /// Returns true if this value is neither NaN nor infinity.
+        /// </summary>
+        /// <param name="d">(this)</param>
+        public static bool IsFinite(this double d)
+        {
+            return !double.IsNaN(d) && !double.IsInfinity(d);
+        }
+
+
+        /// <summary>
+        /// Replace each NaN or infinity in this array with 0. Returns the number of values that were replaced. This is useful for preventing invalid audio data from reaching an audio device or a .wav file, and a non-zero return value indicates that something upstream went wrong.
+        /// </summary>
+        /// <param name="a">(this)</param>
+        /// <param name="length">The length of the data in this array (can be less than the true length).</param>
+        public static int Sanitize(this double[] a, int length)
+        {
+            int count = 0;
+            for (int i = 0; i < length; i++)
+            {
+                if (!a[i].IsFinite())
+                {
+                    a[i] = 0;
+                    count++;
+                }
+            }
+            return count;
+        }
+
+
+        /// <summary>
+        /// Remove single-sample clicks from this array. A click is a sample whose absolute difference from both of its neighbors is greater than threshold. Each click is replaced with the average of its neighbors. Returns the number of clicks that were removed. The first and last samples are never modified.
+        /// </summary>
+        /// <param name="a">(this)</param>
+        /// <param name="length">The length of the data in this array (can be less than the true length).</param>
+        /// <param name="threshold">The threshold, e.g. 0.5.</param>
+        public static int Declick(this double[] a, int length, double threshold)
+        {
+            int count = 0;
+            for (int i = 1; i < length - 1; i++)
+            {
+                if (Math.Abs(a[i] - a[i - 1]) > threshold && Math.Abs(a[i] - a[i + 1]) > threshold)
+                {
+                    a[i] = (a[i - 1] + a[i + 1]) / 2;
+                    count++;
+                }
+            }
+            return count;
+        }
+
+
+        /// <summary>
+        /// Returns this value converted to a 16-bit PCM sample. The value is clamped to be between -1 and 1, scaled by 32767, and rounded to the nearest integer. NaN and infinity are converted to 0.
+        /// </summary>
+        /// <param name="d">(this)</param>
+        public static short ToInt16(this double d)
+        {
+            if (!d.IsFinite())
+            {
+                return 0;
+            }
+            return (short)Math.Round(d.Clamp(-1, 1) * FLOAT_TO_SHORT);
+        }
+
+
+        /// <summary>
+        /// Returns this value converted to a 16-bit PCM sample with TPDF (triangular probability density function) dither. Dither decorrelates the quantization error from the signal, which replaces audible quantization distortion in quiet audio (such as the tail of an impact) with a low level of noise. NaN and infinity are converted to 0.
+        /// </summary>
+        /// <param name="d">(this)</param>
+        /// <param name="dither">The random number generator used for dither. To generate reproducible dither, use a seeded random number generator. If this is null, there is no dither and this is the same as ToInt16().</param>
+        public static short ToInt16(this double d, Random dither)
+        {
+            if (dither == null)
+            {
+                return d.ToInt16();
+            }
+            if (!d.IsFinite())
+            {
+                return 0;
+            }
+            // Add triangular noise with an amplitude of one least significant bit.
+            double s = Math.Round(d.Clamp(-1, 1) * FLOAT_TO_SHORT + dither.NextDouble() - dither.NextDouble());
+            return (short)s.Clamp(-FLOAT_TO_SHORT, FLOAT_TO_SHORT);
+        }
+
+
+        /// <summary>
+        /// Returns this value converted to a 32-bit float PCM sample. The value is clamped to be between -1 and 1. NaN and infinity are converted to 0.
+        /// </summary>
+        /// <param name="d">(this)</param>
+        public static float ToClampedFloat(this double d)
+        {
+            if (!d.IsFinite())
+            {
+                return 0;
+            }
+            return (float)d.Clamp(-1, 1);
+        }
+
+
         /// <summary>
         /// Convolve an array with the given kernel.
+        /// If FFT convolution is estimated to be faster (see: fftConvolutionCost), this calls ConvolveFft() instead.
         /// Source: https://stackoverflow.com/a/7239016
         /// This code is a more optimized version of the source.
         /// </summary>
@@ -47,6 +193,167 @@ namespace Clatter.Core
         /// <param name="length">The length of the convolved array.</param>
         /// <param name="result">The output array.</param>
         public static void Convolve(this double[] a, double[] kernel, int length, ref double[] result)
+        {
+            if (UseFft(a.Length, kernel.Length, length))
+            {
+                a.ConvolveFft(kernel, length, ref result);
+                return;
+            }
+            a.ConvolveDirect(a.Length, kernel, kernel.Length, length, ref result);
+        }
+
+
+        /// <summary>
+        /// Convolve an array with the given kernel and return the full convolution, which has a length of aLength + kernelLength - 1. Unlike Convolve(), the caller doesn't need to calculate the output length. Returns the length of the result.
+        /// </summary>
+        /// <param name="a">(this)</param>
+        /// <param name="aLength">The length of the data in this array (can be less than the true length).</param>
+        /// <param name="kernel">A convolution kernel.</param>
+        /// <param name="kernelLength">The length of the data in the kernel array (can be less than the true length).</param>
+        /// <param name="result">The output array. This will be resized if needed.</param>
+        public static int ConvolveFull(this double[] a, int aLength, double[] kernel, int kernelLength, ref double[] result)
+        {
+            int length = GetConvolutionLength(aLength, kernelLength);
+            if (length == 0)
+            {
+                return 0;
+            }
+            if (UseFft(aLength, kernelLength, length))
+            {
+                a.ConvolveFft(aLength, kernel, kernelLength, length, ref result);
+            }
+            else
+            {
+                a.ConvolveDirect(aLength, kernel, kernelLength, length, ref result);
+            }
+            return length;
+        }
+
+
+        /// <summary>
+        /// Returns the length of the full convolution of an array with a kernel: aLength + kernelLength - 1, or 0 if either length is less than or equal to 0. Use this to size an output array before calling ConvolveFull() or ConvolveFft().
+        /// </summary>
+        /// <param name="aLength">The length of the input array.</param>
+        /// <param name="kernelLength">The length of the kernel.</param>
+        public static int GetConvolutionLength(int aLength, int kernelLength)
+        {
+            if (aLength <= 0 || kernelLength <= 0)
+            {
+                return 0;
+            }
+            return aLength + kernelLength - 1;
+        }
+
+
+        /// <summary>
+        /// Convolve an array with the given kernel and divide the full convolution by the L2 norm of the kernel (the square root of the sum of the squared kernel values). Kernels with different total energy therefore produce output with approximately the same RMS loudness, assuming that the input is noise-like. Returns the length of the result: aLength + kernelLength - 1. If the kernel is silent, the result isn't scaled.
+        /// </summary>
+        /// <param name="a">(this)</param>
+        /// <param name="aLength">The length of the data in this array (can be less than the true length).</param>
+        /// <param name="kernel">A convolution kernel.</param>
+        /// <param name="kernelLength">The length of the data in the kernel array (can be less than the true length).</param>
+        /// <param name="result">The output array. This will be resized if needed.</param>
+        public static int ConvolveNormalized(this double[] a, int aLength, double[] kernel, int kernelLength, ref double[] result)
+        {
+            int length = a.ConvolveFull(aLength, kernel, kernelLength, ref result);
+            double norm = kernel.GetL2Norm(kernelLength);
+            if (norm > 0)
+            {
+                for (int i = 0; i < length; i++)
+                {
+                    result[i] /= norm;
+                }
+            }
+            return length;
+        }
+
+
+        /// <summary>
+        /// Returns the L2 norm of this array: the square root of the sum of the squared values.
+        /// </summary>
+        /// <param name="a">(this)</param>
+        /// <param name="length">The length of the data in this array (can be less than the true length).</param>
+        public static double GetL2Norm(this double[] a, int length)
+        {
+            double sum = 0;
+            for (int i = 0; i < length; i++)
+            {
+                sum += a[i] * a[i];
+            }
+            return Math.Sqrt(sum);
+        }
+
+
+        /// <summary>
+        /// Convolve an array with the given kernel and split the full convolution into a body and a tail. This is the building block of overlap-add convolution (see: `StreamingConvolver`): the tail of one block is added to the body of the next.
+        ///
+        /// Indices 0 to aLength - 1 of the full convolution are copied into the body, and indices aLength to aLength + kernelLength - 2 are copied into the tail. The body therefore has a length of aLength and the tail has a length of kernelLength - 1. Together, they are the same as the result of ConvolveFull().
+        /// </summary>
+        /// <param name="a">(this)</param>
+        /// <param name="aLength">The length of the data in this array (can be less than the true length).</param>
+        /// <param name="kernel">A convolution kernel.</param>
+        /// <param name="kernelLength">The length of the data in the kernel array (can be less than the true length).</param>
+        /// <param name="body">The first aLength samples of the convolution. This will be resized if needed.</param>
+        /// <param name="tail">The remaining kernelLength - 1 samples of the convolution. This will be resized if needed.</param>
+        public static void ConvolveWithTail(this double[] a, int aLength, double[] kernel, int kernelLength, ref double[] body, ref double[] tail)
+        {
+            if (aLength <= 0 || kernelLength <= 0)
+            {
+                return;
+            }
+            if (fullConvolution == null)
+            {
+                fullConvolution = new double[GetConvolutionLength(aLength, kernelLength)];
+            }
+            a.ConvolveFull(aLength, kernel, kernelLength, ref fullConvolution);
+            if (body.Length < aLength)
+            {
+                Array.Resize(ref body, aLength);
+            }
+            int tailLength = kernelLength - 1;
+            if (tail.Length < tailLength)
+            {
+                Array.Resize(ref tail, tailLength);
+            }
+            Buffer.BlockCopy(fullConvolution, 0, body, 0, aLength * 8);
+            Buffer.BlockCopy(fullConvolution, aLength * 8, tail, 0, tailLength * 8);
+        }
+
+
+        /// <summary>
+        /// Returns true if FFT convolution is estimated to be faster than direct convolution. See: fftConvolutionCost.
+        /// </summary>
+        /// <param name="aLength">The length of the input array.</param>
+        /// <param name="kernelLength">The length of the kernel.</param>
+        /// <param name="length">The length of the convolved array.</param>
+        private static bool UseFft(int aLength, int kernelLength, int length)
+        {
+            if (kernelLength < fftConvolutionKernelLength)
+            {
+                return false;
+            }
+            // Values beyond the output length don't affect the output.
+            int inputLength = Math.Min(aLength, length);
+            kernelLength = Math.Min(kernelLength, length);
+            if (inputLength <= 0 || kernelLength <= 0)
+            {
+                return false;
+            }
+            double n = Fft.GetSize(inputLength + kernelLength - 1);
+            return (double)inputLength * kernelLength > fftConvolutionCost * n * Math.Log(n, 2);
+        }
+
+
+        /// <summary>
+        /// Convolve an array with the given kernel without using a Fast Fourier Transform.
+        /// </summary>
+        /// <param name="a">(this)</param>
+        /// <param name="inputLength">The length of the data in this array (can be less than the true length).</param>
+        /// <param name="kernel">A convolution kernel.</param>
+        /// <param name="kernelLength">The length of the data in the kernel array (can be less than the true length).</param>
+        /// <param name="length">The length of the convolved array.</param>
+        /// <param name="result">The output array.</param>
+        private static void ConvolveDirect(this double[] a, int inputLength, double[] kernel, int kernelLength, int length, ref double[] result)
         {
             if (result.Length < length)
             {
@@ -55,14 +362,32 @@ namespace Clatter.Core
             double sum;
             int n1;
             int n2;
-            int inputLength = a.Length;
-            int kernelLength = kernel.Length;
             for (int i = length - 1; i >= 0; i--)
             {
                 sum = 0;
-                n1 = i < inputLength ? 0 : i - inputLength - 1;
+                // result[i] = sum(a[i - j] * kernel[j]) for all j where 0 <= i - j < inputLength and 0 <= j < kernelLength.
+                n1 = i < inputLength ? 0 : i - inputLength + 1;
                 n2 = i < kernelLength ? i : kernelLength - 1;
-                for (int j = n1; j <= n2; j++)
+                int j = n1;
+                if (unrollConvolution)
+                {
+                    double sum0 = 0;
+                    double sum1 = 0;
+                    double sum2 = 0;
+                    double sum3 = 0;
+                    int k;
+                    for (; j + 3 <= n2; j += 4)
+                    {
+                        k = i - j;
+                        sum0 += a[k] * kernel[j];
+                        sum1 += a[k - 1] * kernel[j + 1];
+                        sum2 += a[k - 2] * kernel[j + 2];
+                        sum3 += a[k - 3] * kernel[j + 3];
+                    }
+                    sum = (sum0 + sum1) + (sum2 + sum3);
+                }
+                // Add the remaining taps.
+                for (; j <= n2; j++)
                 {
                     sum += a[i - j] * kernel[j];
                 }
@@ -71,6 +396,121 @@ namespace Clatter.Core
         }
 
 
+        /// <summary>
+        /// Convolve an array with the given kernel using a Fast Fourier Transform. The result is numerically equivalent to direct convolution (within floating-point error) but is much faster for long kernels.
+        /// </summary>
+        /// <param name="a">(this)</param>
+        /// <param name="kernel">A convolution kernel.</param>
+        /// <param name="length">The length of the convolved array.</param>
+        /// <param name="result">The output array.</param>
+        public static void ConvolveFft(this double[] a, double[] kernel, int length, ref double[] result)
+        {
+            a.ConvolveFft(a.Length, kernel, kernel.Length, length, ref result);
+        }
+
+
+        /// <summary>
+        /// Convolve an array with the given kernel using a Fast Fourier Transform. The result is numerically equivalent to direct convolution (within floating-point error) but is much faster for long kernels.
+        /// </summary>
+        /// <param name="a">(this)</param>
+        /// <param name="aLength">The length of the data in this array (can be less than the true length).</param>
+        /// <param name="kernel">A convolution kernel.</param>
+        /// <param name="kernelLength">The length of the data in the kernel array (can be less than the true length).</param>
+        /// <param name="length">The length of the convolved array.</param>
+        /// <param name="result">The output array.</param>
+        public static void ConvolveFft(this double[] a, int aLength, double[] kernel, int kernelLength, int length, ref double[] result)
+        {
+            if (result.Length < length)
+            {
+                Array.Resize(ref result, length * 2);
+            }
+            // Values beyond the output length don't affect the output.
+            int inputLength = Math.Min(aLength, length);
+            kernelLength = Math.Min(kernelLength, length);
+            if (inputLength == 0 || kernelLength == 0)
+            {
+                Array.Clear(result, 0, length);
+                return;
+            }
+            // Zero-pad to the next power of two.
+            int fullLength = inputLength + kernelLength - 1;
+            int n = Fft.GetSize(fullLength);
+            double[] fftInputReal;
+            double[] fftInputImaginary;
+            double[] fftKernelReal;
+            double[] fftKernelImaginary;
+            if (n <= MAX_CACHED_FFT_SIZE)
+            {
+                if (cachedFftInputReal == null || cachedFftInputReal.Length < n)
+                {
+                    cachedFftInputReal = new double[n];
+                    cachedFftInputImaginary = new double[n];
+                    cachedFftKernelReal = new double[n];
+                    cachedFftKernelImaginary = new double[n];
+                }
+                fftInputReal = cachedFftInputReal;
+                fftInputImaginary = cachedFftInputImaginary;
+                fftKernelReal = cachedFftKernelReal;
+                fftKernelImaginary = cachedFftKernelImaginary;
+            }
+            else
+            {
+                fftInputReal = new double[n];
+                fftInputImaginary = new double[n];
+                fftKernelReal = new double[n];
+                fftKernelImaginary = new double[n];
+            }
+            Buffer.BlockCopy(a, 0, fftInputReal, 0, inputLength * 8);
+            Array.Clear(fftInputReal, inputLength, n - inputLength);
+            Array.Clear(fftInputImaginary, 0, n);
+            Buffer.BlockCopy(kernel, 0, fftKernelReal, 0, kernelLength * 8);
+            Array.Clear(fftKernelReal, kernelLength, n - kernelLength);
+            Array.Clear(fftKernelImaginary, 0, n);
+            // Multiply in the frequency domain.
+            Fft.Transform(fftInputReal, fftInputImaginary, n, false);
+            Fft.Transform(fftKernelReal, fftKernelImaginary, n, false);
+            double re;
+            for (int i = 0; i < n; i++)
+            {
+                re = fftInputReal[i] * fftKernelReal[i] - fftInputImaginary[i] * fftKernelImaginary[i];
+                fftInputImaginary[i] = fftInputReal[i] * fftKernelImaginary[i] + fftInputImaginary[i] * fftKernelReal[i];
+                fftInputReal[i] = re;
+            }
+            Fft.Transform(fftInputReal, fftInputImaginary, n, true);
+            // Copy the result.
+            int copyLength = Math.Min(length, fullLength);
+            Buffer.BlockCopy(fftInputReal, 0, result, 0, copyLength * 8);
+            if (copyLength < length)
+            {
+                Array.Clear(result, copyLength, length - copyLength);
+            }
+        }
+
+
+        /// <summary>
+        /// Convolve an array with multiple kernels, for example to convolve the same dry signal with a different impulse response per channel. This calls Convolve() for each kernel.
+        /// </summary>
+        /// <param name="a">(this)</param>
+        /// <param name="kernels">The convolution kernels.</param>
+        /// <param name="length">The length of each convolved array.</param>
+        /// <param name="results">The output arrays. There must be one output array per kernel. Each output array will be resized if needed.</param>
+        public static void ConvolveMulti(this double[] a, double[][] kernels, int length, double[][] results)
+        {
+            if (kernels.Length != results.Length)
+            {
+                throw new Exception("The number of kernels doesn't match the number of output arrays: " + kernels.Length + ", " + results.Length);
+            }
+            for (int i = 0; i < kernels.Length; i++)
+            {
+                if (results[i] == null)
+                {
+                    results[i] = new double[length];
+                }
+                a.Convolve(kernels[i], length, ref results[i]);
+            }
+        }
+
+
         /// <summary>
         /// Interpolates data using a piece-wise linear function.
         /// This has been optimized from the source.
@@ -82,7 +522,7 @@ namespace Clatter.Core
         /// <param name="lower">The value to be returned for values before the first point in <paramref name="x"/>.</param>
         /// <param name="upper">The value to be returned for values after the last point in <paramref name="x"/>.</param>
         /// <param name="yIndexOffset">Offset the y index by this value.</param>
-        /// <param name="startX">Start interpolating the x array at this index.</param>
+        /// <param name="startX">Start interpolating the x array at this index. This is set to the index of the segment containing the value, so that a sequence of increasing values can be interpolated without searching the x array from the start each time.</param>
         /// <param name="endX">The final index in the x array.</param>
         public static double Interpolate1D(this double value, double[] x, double[] y, double lower, double upper, int yIndexOffset, ref int startX, int endX)
         {
@@ -93,7 +533,8 @@ namespace Clatter.Core
             {
                 if (value < x[i])
                 {
-                    startX = i + 1;
+                    // The next value might be in the same segment, so don't skip ahead.
+                    startX = i;
                     if (i == 0)
                     {
                         return lower;
@@ -109,28 +550,113 @@ namespace Clatter.Core
         }
 
 
+        /// <summary>
+        /// Interpolates data using a Catmull-Rom cubic spline. Unlike Interpolate1D(), the slope of the interpolated curve is continuous at each data point, which results in a smoother curve.
+        ///
+        /// The x values are assumed to be evenly spaced. To prevent overshoot, each interpolated value is clamped to the range of the two data points on either side of it.
+        /// </summary>
+        /// <param name="value">The value to be calculated.</param>
+        /// <param name="x">The input data points <c>x</c>. Those values need to be sorted and evenly spaced.</param>
+        /// <param name="y">The output data points <c>y</c>.</param>
+        /// <param name="lower">The value to be returned for values before the first point in <paramref name="x"/>.</param>
+        /// <param name="upper">The value to be returned for values after the last point in <paramref name="x"/>.</param>
+        /// <param name="yIndexOffset">Offset the y index by this value.</param>
+        /// <param name="startX">Start interpolating the x array at this index. This is set to the index of the segment containing the value, so that a sequence of increasing values can be interpolated without searching the x array from the start each time.</param>
+        /// <param name="endX">The final index in the x array.</param>
+        public static double Interpolate1DCubic(this double value, double[] x, double[] y, double lower, double upper, int yIndexOffset, ref int startX, int endX)
+        {
+            for (int i = startX; i < endX; i++)
+            {
+                if (value < x[i])
+                {
+                    // The next value might be in the same segment, so don't skip ahead.
+                    startX = i;
+                    if (i == 0)
+                    {
+                        return lower;
+                    }
+                    int start = i - 1;
+                    double m = (value - x[start]) / (x[i] - x[start]);
+                    // Get the four control points. At the ends of the data, repeat the end point.
+                    double y0 = y[(start > 0 ? start - 1 : start) + yIndexOffset];
+                    double y1 = y[start + yIndexOffset];
+                    double y2 = y[i + yIndexOffset];
+                    double y3 = y[(i + 1 < endX ? i + 1 : i) + yIndexOffset];
+                    double v = 0.5 * (2 * y1 + (y2 - y0) * m + (2 * y0 - 5 * y1 + 4 * y2 - y3) * m * m +
+                                      (3 * (y1 - y2) + y3 - y0) * m * m * m);
+                    // Prevent overshoot.
+                    return y1 < y2 ? v.Clamp(y1, y2) : v.Clamp(y2, y1);
+                }
+            }
+            startX = 0;
+            return upper;
+        }
+
+
         /// <summary>
         /// Returns this array converted to floats.
         /// </summary>
         /// <param name="a">(this)</param>
         /// <param name="length">The length of the converted array.</param>
-        public static float[] ToFloats(this double[] a, int length)
+        /// <param name="clamp">If true, clamp each value to be between -1 and 1 and convert NaN and infinity to 0. See: ToClampedFloat().</param>
+        public static float[] ToFloats(this double[] a, int length, bool clamp = false)
         {
             float[] fs = new float[length];
-            for (int i = 0; i < length; i++)
+            if (clamp)
             {
-                fs[i] = (float)a[i];
+                for (int i = 0; i < length; i++)
+                {
+                    fs[i] = a[i].ToClampedFloat();
+                }
+            }
+            else
+            {
+                for (int i = 0; i < length; i++)
+                {
+                    fs[i] = (float)a[i];
+                }
             }
             return fs;
         }
+
+
+        /// <summary>
+        /// Returns this array converted to 16-bit PCM samples. See: ToInt16().
+        /// </summary>
+        /// <param name="a">(this)</param>
+        /// <param name="length">The length of the converted array.</param>
+        /// <param name="dither">The random number generator used for dither. If this is null, there is no dither.</param>
+        public static short[] ToInt16s(this double[] a, int length, Random dither = null)
+        {
+            short[] ss = new short[length];
+            for (int i = 0; i < length; i++)
+            {
+                ss[i] = a[i].ToInt16(dither);
+            }
+            return ss;
+        }
         
         
         /// <summary>
-        /// Returns this array converted to a byte array of int16s.
+        /// Returns this array converted to a byte array of float32s.
         /// </summary>
         /// <param name="a">(this)</param>
         /// <param name="length">The length of the converted array.</param>
-        public static byte[] ToInt16Bytes(this double[] a, int length)
+        public static byte[] ToFloatBytes(this double[] a, int length)
+        {
+            byte[] bs = new byte[length * 4];
+            Buffer.BlockCopy(a.ToFloats(length), 0, bs, 0, bs.Length);
+            return bs;
+        }
+        
+        
+        /// <summary>
+        /// Returns this array converted to a byte array of int16s. See: ToInt16().
+        /// </summary>
+        /// <param name="a">(this)</param>
+        /// <param name="length">The length of the converted array.</param>
+        /// <param name="dither">The random number generator used for dither. If this is null, there is no dither.</param>
+        public static byte[] ToInt16Bytes(this double[] a, int length, Random dither = null)
         {
             byte[] bs = new byte[length * 2];
             byte[] shortArray = new byte[2];
@@ -139,8 +665,8 @@ namespace Clatter.Core
             // Source: https://gist.github.com/darktable/2317063
             for (int i = 0; i < length; i++)
             {
-                // Cast to short.
-                s = (short)(a[i] * FLOAT_TO_SHORT);
+                // Convert to short.
+                s = a[i].ToInt16(dither);
                 // Convert to bytes.
                 s.GetBytes(shortArray);
                 // Copy the bytes
@@ -155,9 +681,13 @@ namespace Clatter.Core
         /// WARNING: Works inplace and can thus causes the data array to be reordered.
         /// Source: https://github.com/mathnet/mathnet-numerics/blob/master/src/Numerics/Statistics/ArrayStatistics.cs#L413
         /// </summary>
-        /// <param name="data">Sample array, no sorting is assumed. Will be reordered.</param>
+        /// <param name="data">Sample array, no sorting is assumed. Will be reordered. Must not be empty.</param>
         public static double MedianInPlace(this double[] data)
         {
+            if (data.Length == 0)
+            {
+                throw new Exception("Can't get the median of an empty array.");
+            }
             int k = data.Length / 2;
             return data.Length % 2 != 0
                 ? data.SelectInPlace(k)
@@ -172,6 +702,13 @@ namespace Clatter.Core
         /// <param name="rank">The rank value.</param>
         private static double SelectInPlace(this double[] workingData, int rank)
         {
+            // NaN breaks the comparisons that the selection algorithm relies upon, which can cause it to read past the end of the array.
+            // In this case, fall back to a full sort, which always terminates and orders NaN before all other values.
+            if (workingData.HasNaN())
+            {
+                Array.Sort(workingData);
+                return workingData[rank < 0 ? 0 : rank >= workingData.Length ? workingData.Length - 1 : rank];
+            }
             // Numerical Recipes: select
             // http://en.wikipedia.org/wiki/Selection_algorithm
             if (rank <= 0)
@@ -246,6 +783,23 @@ namespace Clatter.Core
         }
         
         
+        /// <summary>
+        /// Returns true if the data array contains any NaN values.
+        /// </summary>
+        /// <param name="data">(this)</param>
+        private static bool HasNaN(this double[] data)
+        {
+            for (int i = 0; i < data.Length; i++)
+            {
+                if (double.IsNaN(data[i]))
+                {
+                    return true;
+                }
+            }
+            return false;
+        }
+
+
         /// <summary>
         /// Returns the smallest value from the unsorted data array.
         /// This assumes that the data has a length greater than zero and no NaN values.
@@ -0,0 +1,70 @@
+﻿using System;
+
+
+namespace Clatter.Core
+{
+    /// <summary>
+    /// Adjust the loudness of audio samples.
+    /// </summary>
+    public static class Dynamics
+    {
+        /// <summary>
+        /// Scale the samples so that their absolute peak equals targetPeak. Returns the gain that was applied. If the samples are empty or silent, they aren't modified and this returns 1.
+        /// </summary>
+        /// <param name="samples">The audio samples. This array will be modified.</param>
+        /// <param name="length">The length of the audio data (can be less than the true length of the array).</param>
+        /// <param name="targetPeak">The target absolute peak value, e.g. 0.9.</param>
+        public static double NormalizePeak(double[] samples, int length, double targetPeak)
+        {
+            double peak = 0;
+            double abs;
+            for (int i = 0; i < length; i++)
+            {
+                abs = Math.Abs(samples[i]);
+                if (abs > peak)
+                {
+                    peak = abs;
+                }
+            }
+            if (peak <= 0)
+            {
+                return 1;
+            }
+            double gain = targetPeak / peak;
+            for (int i = 0; i < length; i++)
+            {
+                samples[i] *= gain;
+            }
+            return gain;
+        }
+
+
+        /// <summary>
+        /// Apply a tanh-shaped soft clipper to the samples. Samples whose absolute value is below the threshold aren't modified. Samples above the threshold are smoothly compressed such that they approach but never exceed 1.
+        /// </summary>
+        /// <param name="samples">The audio samples. This array will be modified.</param>
+        /// <param name="length">The length of the audio data (can be less than the true length of the array).</param>
+        /// <param name="threshold">The threshold (0 to 1). At 0, every sample is passed through tanh. If this is 1 or greater, the samples aren't modified.</param>
+        public static void SoftClip(double[] samples, int length, double threshold)
+        {
+            if (threshold >= 1)
+            {
+                return;
+            }
+            if (threshold < 0)
+            {
+                threshold = 0;
+            }
+            double knee = 1 - threshold;
+            double abs;
+            for (int i = 0; i < length; i++)
+            {
+                abs = Math.Abs(samples[i]);
+                if (abs > threshold)
+                {
+                    samples[i] = Math.Sign(samples[i]) * (threshold + knee * Math.Tanh((abs - threshold) / knee));
+                }
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,206 @@
+﻿using System;
+
+
+namespace Clatter.Core
+{
+    /// <summary>
+    /// Shape the amplitude envelope of audio samples.
+    /// </summary>
+    public static class Envelope
+    {
+        /// <summary>
+        /// Multiply the samples by an ADSR (attack, decay, sustain, release) envelope. The release ends at the end of the audio data.
+        ///
+        /// If the attack, decay, and release are longer than the audio data, they are clamped in that order: the attack is clamped to the length of the data, then the decay is clamped to the remaining length, then the release.
+        /// </summary>
+        /// <param name="samples">The audio samples. This array will be modified.</param>
+        /// <param name="length">The length of the audio data (can be less than the true length of the array).</param>
+        /// <param name="attack">The duration of the attack in milliseconds, during which the envelope rises from 0 to 1. If this is 0, the onset is instant.</param>
+        /// <param name="decay">The duration of the decay in milliseconds, during which the envelope falls from 1 to the sustain level.</param>
+        /// <param name="sustain">The sustain level (0 to 1).</param>
+        /// <param name="release">The duration of the release in milliseconds, during which the envelope falls from the sustain level to 0.</param>
+        public static void ApplyAdsr(double[] samples, int length, double attack, double decay, double sustain, double release)
+        {
+            int attackLength = Math.Min(MsToLength(attack), length);
+            int decayLength = Math.Min(MsToLength(decay), length - attackLength);
+            int releaseLength = Math.Min(MsToLength(release), length - attackLength - decayLength);
+            int releaseStart = length - releaseLength;
+            int i = 0;
+            // Attack.
+            for (; i < attackLength; i++)
+            {
+                samples[i] *= (double)i / attackLength;
+            }
+            // Decay.
+            for (int j = 0; j < decayLength; j++, i++)
+            {
+                samples[i] *= 1 - (1 - sustain) * j / decayLength;
+            }
+            // Sustain.
+            for (; i < releaseStart; i++)
+            {
+                samples[i] *= sustain;
+            }
+            // Release. The final sample is 0.
+            for (int j = 0; j < releaseLength; j++, i++)
+            {
+                samples[i] *= sustain * (releaseLength - 1 - j) / Math.Max(releaseLength - 1, 1);
+            }
+        }
+
+
+        /// <summary>
+        /// Fade the start and end of the samples in and out. This can remove clicks at the start and end of truncated audio. After a non-zero fade, the first and/or last sample is 0.
+        ///
+        /// If the fades are longer than the audio data, they are proportionally shortened so that they don't overlap.
+        /// </summary>
+        /// <param name="samples">The audio samples. This array will be modified.</param>
+        /// <param name="length">The length of the audio data (can be less than the true length of the array).</param>
+        /// <param name="fadeInLength">The length of the fade-in in samples.</param>
+        /// <param name="fadeOutLength">The length of the fade-out in samples.</param>
+        /// <param name="raisedCosine">If true, the fades are raised-cosine curves, which sound smoother. If false, the fades are linear.</param>
+        public static void Fade(double[] samples, int length, int fadeInLength, int fadeOutLength, bool raisedCosine = false)
+        {
+            fadeInLength = Math.Max(fadeInLength, 0);
+            fadeOutLength = Math.Max(fadeOutLength, 0);
+            if (fadeInLength + fadeOutLength > length)
+            {
+                fadeInLength = (int)((long)fadeInLength * length / (fadeInLength + fadeOutLength));
+                fadeOutLength = length - fadeInLength;
+            }
+            for (int i = 0; i < fadeInLength; i++)
+            {
+                samples[i] *= GetFadeGain(i, fadeInLength, raisedCosine);
+            }
+            for (int i = 0; i < fadeOutLength; i++)
+            {
+                samples[length - 1 - i] *= GetFadeGain(i, fadeOutLength, raisedCosine);
+            }
+        }
+
+
+        /// <summary>
+        /// Multiply the samples by a gain that ramps linearly from startGain to endGain. This avoids "zipper noise" when the gain changes between consecutive blocks of audio. If the gains are equal, this multiplies the samples by a constant gain.
+        /// </summary>
+        /// <param name="samples">The audio samples. This array will be modified.</param>
+        /// <param name="length">The length of the audio data (can be less than the true length of the array).</param>
+        /// <param name="startGain">The gain at the start of the audio data, usually the gain at the end of the previous block.</param>
+        /// <param name="endGain">The gain at the end of the audio data.</param>
+        public static void ApplyGainRamp(double[] samples, int length, double startGain, double endGain)
+        {
+            if (startGain == endGain)
+            {
+                for (int i = 0; i < length; i++)
+                {
+                    samples[i] *= endGain;
+                }
+                return;
+            }
+            double step = (endGain - startGain) / length;
+            for (int i = 0; i < length; i++)
+            {
+                samples[i] *= startGain + step * (i + 1);
+            }
+        }
+
+
+        /// <summary>
+        /// Reverse the order of the samples in-place. This can be useful for auditioning impulse responses.
+        /// </summary>
+        /// <param name="samples">The audio samples. This array will be modified.</param>
+        /// <param name="length">The length of the audio data (can be less than the true length of the array).</param>
+        public static void Reverse(double[] samples, int length)
+        {
+            Array.Reverse(samples, 0, length);
+        }
+
+
+        /// <summary>
+        /// Multiply the samples by an exponential taper: sample n is multiplied by 10^(-decay * n / (20 * framerate)). This can be useful for shaping impulse responses.
+        /// </summary>
+        /// <param name="samples">The audio samples. This array will be modified.</param>
+        /// <param name="length">The length of the audio data (can be less than the true length of the array).</param>
+        /// <param name="decay">The decay in dB per second.</param>
+        public static void ApplyExponentialTaper(double[] samples, int length, double decay)
+        {
+            double step = Math.Pow(10, -decay / (20 * Globals.framerateD));
+            double gain = 1;
+            for (int i = 0; i < length; i++)
+            {
+                samples[i] *= gain;
+                gain *= step;
+            }
+        }
+
+
+        /// <summary>
+        /// Copy the samples without their leading and trailing silence: samples whose absolute value is below a threshold. This can save memory if many impacts are stored, because synthesized impacts usually have a long, nearly silent tail. Returns the trimmed length. If every sample is below the threshold, the first sample is kept and this returns 1.
+        /// </summary>
+        /// <param name="samples">The audio samples.</param>
+        /// <param name="length">The length of the audio data (can be less than the true length of the array). This must be greater than 0.</param>
+        /// <param name="threshold">The threshold in dBFS, e.g. -60.</param>
+        /// <param name="output">The trimmed audio samples. This can be the same array as samples. This will be resized if needed.</param>
+        public static int TrimSilence(double[] samples, int length, double threshold, ref double[] output)
+        {
+            if (length <= 0)
+            {
+                throw new Exception("Can't trim empty audio data.");
+            }
+            double linearThreshold = Math.Pow(10, threshold / 20);
+            int start = 0;
+            while (start < length && Math.Abs(samples[start]) < linearThreshold)
+            {
+                start++;
+            }
+            int end = length;
+            // If every sample is silent, keep the first sample.
+            if (start == length)
+            {
+                start = 0;
+                end = 1;
+            }
+            else
+            {
+                while (Math.Abs(samples[end - 1]) < linearThreshold)
+                {
+                    end--;
+                }
+            }
+            int trimmedLength = end - start;
+            if (output.Length < trimmedLength)
+            {
+                Array.Resize(ref output, trimmedLength);
+            }
+            // Buffer.BlockCopy handles overlapping regions, so the output can be the same array as the input.
+            Buffer.BlockCopy(samples, start * 8, output, 0, trimmedLength * 8);
+            return trimmedLength;
+        }
+
+
+        /// <summary>
+        /// Returns the gain of a fade, from 0 at index 0 towards 1 at the end of the fade.
+        /// </summary>
+        /// <param name="index">The index within the fade.</param>
+        /// <param name="fadeLength">The length of the fade.</param>
+        /// <param name="raisedCosine">If true, the fade is a raised-cosine curve. If false, the fade is linear.</param>
+        private static double GetFadeGain(int index, int fadeLength, bool raisedCosine)
+        {
+            double t = (double)index / fadeLength;
+            return raisedCosine ? 0.5 - 0.5 * Math.Cos(Math.PI * t) : t;
+        }
+
+
+        /// <summary>
+        /// Returns a duration in milliseconds converted to a number of samples.
+        /// </summary>
+        /// <param name="ms">The duration in milliseconds.</param>
+        private static int MsToLength(double ms)
+        {
+            if (ms <= 0)
+            {
+                return 0;
+            }
+            return (int)Math.Round(ms / 1e3 * Globals.framerateD);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,87 @@
+﻿namespace Clatter.Core
+{
+    /// <summary>
+    /// The parameters of a single audio event generated by ExternalEntryPoint.GetAudio() or ExternalEntryPoint.TryGetAudio(). Grouping the parameters into an object makes it much harder for an external application to accidentally swap two of them.
+    ///
+    /// Enum values are stored as bytes so that non-C# applications can set them. Use the numerical values found in the `ImpactMaterial`, `AudioEventType`, and `ScrapeMaterial` documents.
+    /// </summary>
+    public class ExternalAudioParameters
+    {
+        /// <summary>
+        /// The primary object's impact material as a byte.
+        /// </summary>
+        public byte primaryImpactMaterial;
+        /// <summary>
+        /// The primary object's amp (0 to 1). This affects the overall loudness of audio generated by this object.
+        /// </summary>
+        public double primaryAmp;
+        /// <summary>
+        /// The primary object's resonance. This affects the decay times of audio generated by this object. The value is clamped to be at least 0 and usually should be below 1.
+        /// </summary>
+        public double primaryResonance;
+        /// <summary>
+        /// The primary object's mass in kilograms.
+        /// </summary>
+        public double primaryMass;
+        /// <summary>
+        /// The secondary object's impact material as a byte.
+        /// </summary>
+        public byte secondaryImpactMaterial;
+        /// <summary>
+        /// The secondary object's amp (0 to 1). This affects the overall loudness of audio generated by this object.
+        /// </summary>
+        public double secondaryAmp;
+        /// <summary>
+        /// The secondary object's resonance. This affects the decay times of audio generated by this object. The value is clamped to be at least 0 and usually should be below 1.
+        /// </summary>
+        public double secondaryResonance;
+        /// <summary>
+        /// The secondary object's mass in kilograms.
+        /// </summary>
+        public double secondaryMass;
+        /// <summary>
+        /// The speed of the collision in meters per second.
+        /// </summary>
+        public double speed;
+        /// <summary>
+        /// The audio event type as a byte.
+        /// </summary>
+        public byte audioEventType = (byte)AudioEventType.impact;
+        /// <summary>
+        /// The scrape material as a byte. This is used only if the audio event is a scrape.
+        /// </summary>
+        public byte scrapeMaterial;
+        /// <summary>
+        /// The duration of the event in seconds. This is used only if the audio event is a scrape.
+        /// </summary>
+        public double duration;
+        /// <summary>
+        /// If true, use `seed`. If false, the random seed is random.
+        /// </summary>
+        public bool hasSeed;
+        /// <summary>
+        /// The random seed. This is used only if hasSeed == true.
+        /// </summary>
+        public int seed;
+        /// <summary>
+        /// The overall amplitude of the simulation. The amplitude of generated audio is scaled by this factor. Must be between 0 and 0.99.
+        /// </summary>
+        public double simulationAmp = 0.9;
+        /// <summary>
+        /// For the purposes of scrape audio generation, the collision speed is clamped to this maximum value in meters per second.
+        /// </summary>
+        public double scrapeMaxSpeed = 5;
+        /// <summary>
+        /// If true, clamp an impact's audio amplitude values to less than or equal to 0.99, preventing distortion.
+        /// </summary>
+        public bool preventDistortion = true;
+        /// <summary>
+        /// If true, clamp an impact's contact time to a plausible value. Set this to false if you want to generate impacts with unusually long contact times.
+        /// </summary>
+        public bool clampContactTime = true;
+        /// <summary>
+        /// The audio samples framerate.
+        /// </summary>
+        public int framerate = 44100;
+    }
+}
\ No newline at end of file
@@ -6,67 +6,63 @@ namespace Clatter.Core
     /// A convenient entry point for external (non-C#) applications for generating single-event Clatter audio.
     ///
     /// This class necessarily offers a subset of Clatter's full functionality. If you're programming in a C# and/or Unity context, don't use this class! Use the rest of the Clatter API instead.
+    ///
+    /// GetAudio() and TryGetAudio() aren't thread-safe because they set static fields such as Globals.framerate and AudioEvent.simulationAmp. Don't call them from multiple threads at once.
     /// </summary>
     public static class ExternalEntryPoint
     {
+        /// <summary>
+        /// The version of this class's interface. This is incremented whenever the signature of a method in this class changes, or whenever the numerical values of an enum used by this class (such as `ImpactMaterial` or `SynthesisStatus`) change. External applications can compare this to the version that they were written for in order to detect mismatched bindings.
+        /// </summary>
+        public const uint INTERFACE_VERSION = 2;
+
+
+        /// <summary>
+        /// Returns INTERFACE_VERSION. This is useful for external applications that can call methods but can't read constants.
+        /// </summary>
+        public static uint GetInterfaceVersion()
+        {
+            return INTERFACE_VERSION;
+        }
+
+
         /// <summary>
         /// Generate audio. Returns a byte array of int16 data.
         /// </summary>
-        /// <param name="primaryImpactMaterial">The primary object's impact material as a byte. Use the numerical values found in the `ImpactMaterial` document.</param>
-        /// <param name="primaryAmp">The primary object's amp (0 to 1). This affects the overall loudness of audio generated by this object.</param>
-        /// <param name="primaryResonance">The primary object's resonance. This affects the decay times of audio generated by this object. The value is clamped to be at least 0 and usually should be below 1.</param>
-        /// <param name="primaryMass">The primary object's mass in kilograms.</param>
-        /// <param name="secondaryImpactMaterial">The secondary object's impact material as a byte. Use the numerical values found in the `ImpactMaterial` document.</param>
-        /// <param name="secondaryAmp">The secondary object's amp (0 to 1). This affects the overall loudness of audio generated by this object.</param>
-        /// <param name="secondaryResonance">The secondary object's resonance. This affects the decay times of audio generated by this object. The value is clamped to be at least 0 and usually should be below 1.</param>
-        /// <param name="secondaryMass">The secondary object's mass in kilograms.</param>
-        /// <param name="speed">The speed of the collision in meters per second.</param>
-        /// <param name="audioEventType">The audio event type. Use the numerical values found in the `AudioEventType` document.</param>
-        /// <param name="scrapeMaterial">The scrape material. This is used only if the audio event is a scrape. Use the numerical values found in the `ScrapeMaterial` document.</param>
-        /// <param name="duration">The duration of the event is seconds. This is used only if the audio event is a scrape.</param>
-        /// <param name="hasSeed">If true, use the random seed below. If false, the random seed is random.</param>
-        /// <param name="seed">The random seed. This is used only if hasSeed == true.</param>
-        /// <param name="simulationAmp">The overall amplitude of the simulation. The amplitude of generated audio is scaled by this factor. Must be between 0 and 0.99. In most cases, set this to 0.9.</param>
-        /// <param name="scrapeMaxSpeed">For the purposes of scrape audio generation, the collision speed is clamped to this maximum value in meters per second. In most cases, set this to 5.</param>
-        /// <param name="preventDistortion">If true, clamp an impact's audio amplitude values to less than or equal to 0.99, preventing distortion. In most cases, set this to true.</param>
-        /// <param name="clampContactTime">If true, clamp an impact's contact time to a plausible value. Set this to false if you want to generate impacts with unusually long contact times. In most cases, set this to true.</param>
-        /// <param name="framerate">The audio samples framerate. In most cases, set this to 44100</param>
-        public static byte[] GetAudio(byte primaryImpactMaterial, double primaryAmp, double primaryResonance, double primaryMass,
-            byte secondaryImpactMaterial, double secondaryAmp, double secondaryResonance, double secondaryMass,
-            double speed, byte audioEventType, byte scrapeMaterial, double duration, bool hasSeed, int seed,
-            double simulationAmp, double scrapeMaxSpeed, bool preventDistortion, bool clampContactTime, int framerate)
+        /// <param name="parameters">The parameters of the audio event.</param>
+        public static byte[] GetAudio(ExternalAudioParameters parameters)
         {
             // Set static variables.
-            AudioEvent.simulationAmp = simulationAmp.Clamp(0, 0.99);
-            Scrape.maxSpeed = scrapeMaxSpeed;
-            Impact.preventDistortion = preventDistortion;
-            Impact.clampContactTime = clampContactTime;
-            Globals.framerate = framerate;
-            Globals.framerateD = framerate;
+            AudioEvent.simulationAmp = parameters.simulationAmp.Clamp(0, 0.99);
+            Scrape.maxSpeed = parameters.scrapeMaxSpeed;
+            Impact.preventDistortion = parameters.preventDistortion;
+            Impact.clampContactTime = parameters.clampContactTime;
+            Globals.framerate = parameters.framerate;
+            Globals.framerateD = parameters.framerate;
             // Get the impact materials.
-            ImpactMaterial p = GetImpactMaterial(primaryImpactMaterial);
-            ImpactMaterial s = GetImpactMaterial(secondaryImpactMaterial);
+            ImpactMaterial p = GetImpactMaterial(parameters.primaryImpactMaterial);
+            ImpactMaterial s = GetImpactMaterial(parameters.secondaryImpactMaterial);
             // Get the objects.
-            ClatterObjectData primary = new ClatterObjectData(0, p, primaryAmp, primaryResonance, primaryMass);
-            ClatterObjectData secondary = new ClatterObjectData(1, s, secondaryAmp, secondaryResonance, secondaryMass);
+            ClatterObjectData primary = new ClatterObjectData(0, p, parameters.primaryAmp, parameters.primaryResonance, parameters.primaryMass);
+            ClatterObjectData secondary = new ClatterObjectData(1, s, parameters.secondaryAmp, parameters.secondaryResonance, parameters.secondaryMass);
             // Get the random number generator.
             Random rng;
-            if (hasSeed)
+            if (parameters.hasSeed)
             {
-                rng = new Random(seed);
+                rng = new Random(parameters.seed);
             }
             else
             {
                 rng = new Random();
             }
-            AudioEventType type = (AudioEventType)audioEventType;
+            AudioEventType type = (AudioEventType)parameters.audioEventType;
             byte[] audio = Array.Empty<byte>();
             // Generate impact audio.
             if (type == AudioEventType.impact)
             {
                 Impact impact = new Impact(primary, secondary, rng);
                 // Generate audio.
-                bool ok = impact.GetAudio(speed);
+                bool ok = impact.GetAudio(parameters.speed);
                 if (ok)
                 {
                     audio = impact.samples.ToInt16Bytes();
@@ -75,20 +71,20 @@ namespace Clatter.Core
             else if (type == AudioEventType.scrape)
             {
                 // Load the scrape material.
-                ScrapeMaterial sm = (ScrapeMaterial)scrapeMaterial;
+                ScrapeMaterial sm = (ScrapeMaterial)parameters.scrapeMaterial;
                 ScrapeMaterialData.Load(sm);
                 // Set the speed of the primary object.
-                primary.speed = speed;
+                primary.speed = parameters.speed;
                 // Get the number of scrape events.
-                int count = Scrape.GetNumScrapeEvents(duration);
+                int count = Scrape.GetNumScrapeEvents(parameters.duration);
                 // Get the scrape.
                 Scrape scrape = new Scrape(sm, primary, secondary, new Random());
-                audio = new byte[Scrape.SAMPLES_LENGTH * 2 * count];
-                int c = Scrape.SAMPLES_LENGTH * 2;
+                audio = new byte[Scrape.samplesLength * 2 * count];
+                int c = Scrape.samplesLength * 2;
                 for (int i = 0; i < count; i++)
                 {
                     // Continue the scrape.
-                    scrape.GetAudio(speed);
+                    scrape.GetAudio(parameters.speed);
                     // Get the audio and copy it to the buffer.
                     Buffer.BlockCopy(scrape.samples.ToInt16Bytes(), 0, audio, i * c, c);
                 }
@@ -99,6 +95,170 @@ namespace Clatter.Core
         
         
         
+        /// <summary>
+        /// Generate audio. Returns a byte array of int16 data. This is the same as GetAudio(ExternalAudioParameters) and will be removed in a future version.
+        /// </summary>
+        /// <param name="primaryImpactMaterial">The primary object's impact material as a byte. Use the numerical values found in the `ImpactMaterial` document.</param>
+        /// <param name="primaryAmp">The primary object's amp (0 to 1). This affects the overall loudness of audio generated by this object.</param>
+        /// <param name="primaryResonance">The primary object's resonance. This affects the decay times of audio generated by this object. The value is clamped to be at least 0 and usually should be below 1.</param>
+        /// <param name="primaryMass">The primary object's mass in kilograms.</param>
+        /// <param name="secondaryImpactMaterial">The secondary object's impact material as a byte. Use the numerical values found in the `ImpactMaterial` document.</param>
+        /// <param name="secondaryAmp">The secondary object's amp (0 to 1). This affects the overall loudness of audio generated by this object.</param>
+        /// <param name="secondaryResonance">The secondary object's resonance. This affects the decay times of audio generated by this object. The value is clamped to be at least 0 and usually should be below 1.</param>
+        /// <param name="secondaryMass">The secondary object's mass in kilograms.</param>
+        /// <param name="speed">The speed of the collision in meters per second.</param>
+        /// <param name="audioEventType">The audio event type. Use the numerical values found in the `AudioEventType` document.</param>
+        /// <param name="scrapeMaterial">The scrape material. This is used only if the audio event is a scrape. Use the numerical values found in the `ScrapeMaterial` document.</param>
+        /// <param name="duration">The duration of the event is seconds. This is used only if the audio event is a scrape.</param>
+        /// <param name="hasSeed">If true, use the random seed below. If false, the random seed is random.</param>
+        /// <param name="seed">The random seed. This is used only if hasSeed == true.</param>
+        /// <param name="simulationAmp">The overall amplitude of the simulation. The amplitude of generated audio is scaled by this factor. Must be between 0 and 0.99. In most cases, set this to 0.9.</param>
+        /// <param name="scrapeMaxSpeed">For the purposes of scrape audio generation, the collision speed is clamped to this maximum value in meters per second. In most cases, set this to 5.</param>
+        /// <param name="preventDistortion">If true, clamp an impact's audio amplitude values to less than or equal to 0.99, preventing distortion. In most cases, set this to true.</param>
+        /// <param name="clampContactTime">If true, clamp an impact's contact time to a plausible value. Set this to false if you want to generate impacts with unusually long contact times. In most cases, set this to true.</param>
+        /// <param name="framerate">The audio samples framerate. In most cases, set this to 44100</param>
+        [Obsolete("Use GetAudio(ExternalAudioParameters) instead.")]
+        public static byte[] GetAudio(byte primaryImpactMaterial, double primaryAmp, double primaryResonance, double primaryMass,
+            byte secondaryImpactMaterial, double secondaryAmp, double secondaryResonance, double secondaryMass,
+            double speed, byte audioEventType, byte scrapeMaterial, double duration, bool hasSeed, int seed,
+            double simulationAmp, double scrapeMaxSpeed, bool preventDistortion, bool clampContactTime, int framerate)
+        {
+            ExternalAudioParameters parameters = new ExternalAudioParameters
+            {
+                primaryImpactMaterial = primaryImpactMaterial,
+                primaryAmp = primaryAmp,
+                primaryResonance = primaryResonance,
+                primaryMass = primaryMass,
+                secondaryImpactMaterial = secondaryImpactMaterial,
+                secondaryAmp = secondaryAmp,
+                secondaryResonance = secondaryResonance,
+                secondaryMass = secondaryMass,
+                speed = speed,
+                audioEventType = audioEventType,
+                scrapeMaterial = scrapeMaterial,
+                duration = duration,
+                hasSeed = hasSeed,
+                seed = seed,
+                simulationAmp = simulationAmp,
+                scrapeMaxSpeed = scrapeMaxSpeed,
+                preventDistortion = preventDistortion,
+                clampContactTime = clampContactTime,
+                framerate = framerate
+            };
+            return GetAudio(parameters);
+        }
+
+
+        /// <summary>
+        /// Try to generate audio. Unlike GetAudio(), this never throws an exception. Instead, the input values are validated and the returned status indicates whether audio was generated. If the status isn't SynthesisStatus.ok, audio is an empty array.
+        /// </summary>
+        /// <param name="parameters">The parameters of the audio event.</param>
+        /// <param name="audio">The generated audio as a byte array of int16 data.</param>
+        public static SynthesisStatus TryGetAudio(ExternalAudioParameters parameters, out byte[] audio)
+        {
+            audio = Array.Empty<byte>();
+            if (parameters == null)
+            {
+                return SynthesisStatus.empty_input;
+            }
+            // Validate the enum values.
+            if (!Enum.IsDefined(typeof(ImpactMaterial), parameters.primaryImpactMaterial) || 
+                !Enum.IsDefined(typeof(ImpactMaterial), parameters.secondaryImpactMaterial) ||
+                !Enum.IsDefined(typeof(AudioEventType), parameters.audioEventType))
+            {
+                return SynthesisStatus.out_of_bounds;
+            }
+            AudioEventType type = (AudioEventType)parameters.audioEventType;
+            if (type == AudioEventType.scrape && !Enum.IsDefined(typeof(ScrapeMaterial), parameters.scrapeMaterial))
+            {
+                return SynthesisStatus.out_of_bounds;
+            }
+            // Validate the numerical values.
+            if (!parameters.primaryAmp.IsFinite() || !parameters.primaryResonance.IsFinite() || !parameters.primaryMass.IsFinite() ||
+                !parameters.secondaryAmp.IsFinite() || !parameters.secondaryResonance.IsFinite() || !parameters.secondaryMass.IsFinite() ||
+                !parameters.speed.IsFinite() || !parameters.simulationAmp.IsFinite() || !parameters.scrapeMaxSpeed.IsFinite() ||
+                (type == AudioEventType.scrape && !parameters.duration.IsFinite()))
+            {
+                return SynthesisStatus.non_finite;
+            }
+            if (parameters.framerate <= 0)
+            {
+                return SynthesisStatus.out_of_bounds;
+            }
+            // Roll and none events can't generate audio, nor can a scrape with no events.
+            if (type == AudioEventType.roll || type == AudioEventType.none || 
+                (type == AudioEventType.scrape && parameters.duration * parameters.framerate < Scrape.samplesLength))
+            {
+                return SynthesisStatus.empty_input;
+            }
+            try
+            {
+                audio = GetAudio(parameters);
+            }
+            catch (Exception)
+            {
+                audio = Array.Empty<byte>();
+                return SynthesisStatus.error;
+            }
+            return SynthesisStatus.ok;
+        }
+        
+        
+        /// <summary>
+        /// Try to generate audio. Unlike GetAudio(), this never throws an exception. Instead, the input values are validated and the returned status indicates whether audio was generated. If the status isn't SynthesisStatus.ok, audio is an empty array. This is the same as TryGetAudio(ExternalAudioParameters) and will be removed in a future version.
+        /// </summary>
+        /// <param name="primaryImpactMaterial">The primary object's impact material as a byte. Use the numerical values found in the `ImpactMaterial` document.</param>
+        /// <param name="primaryAmp">The primary object's amp (0 to 1). This affects the overall loudness of audio generated by this object.</param>
+        /// <param name="primaryResonance">The primary object's resonance. This affects the decay times of audio generated by this object. The value is clamped to be at least 0 and usually should be below 1.</param>
+        /// <param name="primaryMass">The primary object's mass in kilograms.</param>
+        /// <param name="secondaryImpactMaterial">The secondary object's impact material as a byte. Use the numerical values found in the `ImpactMaterial` document.</param>
+        /// <param name="secondaryAmp">The secondary object's amp (0 to 1). This affects the overall loudness of audio generated by this object.</param>
+        /// <param name="secondaryResonance">The secondary object's resonance. This affects the decay times of audio generated by this object. The value is clamped to be at least 0 and usually should be below 1.</param>
+        /// <param name="secondaryMass">The secondary object's mass in kilograms.</param>
+        /// <param name="speed">The speed of the collision in meters per second.</param>
+        /// <param name="audioEventType">The audio event type. Use the numerical values found in the `AudioEventType` document.</param>
+        /// <param name="scrapeMaterial">The scrape material. This is used only if the audio event is a scrape. Use the numerical values found in the `ScrapeMaterial` document.</param>
+        /// <param name="duration">The duration of the event is seconds. This is used only if the audio event is a scrape.</param>
+        /// <param name="hasSeed">If true, use the random seed below. If false, the random seed is random.</param>
+        /// <param name="seed">The random seed. This is used only if hasSeed == true.</param>
+        /// <param name="simulationAmp">The overall amplitude of the simulation. The amplitude of generated audio is scaled by this factor. Must be between 0 and 0.99. In most cases, set this to 0.9.</param>
+        /// <param name="scrapeMaxSpeed">For the purposes of scrape audio generation, the collision speed is clamped to this maximum value in meters per second. In most cases, set this to 5.</param>
+        /// <param name="preventDistortion">If true, clamp an impact's audio amplitude values to less than or equal to 0.99, preventing distortion. In most cases, set this to true.</param>
+        /// <param name="clampContactTime">If true, clamp an impact's contact time to a plausible value. Set this to false if you want to generate impacts with unusually long contact times. In most cases, set this to true.</param>
+        /// <param name="framerate">The audio samples framerate. In most cases, set this to 44100</param>
+        /// <param name="audio">The generated audio as a byte array of int16 data.</param>
+        [Obsolete("Use TryGetAudio(ExternalAudioParameters) instead.")]
+        public static SynthesisStatus TryGetAudio(byte primaryImpactMaterial, double primaryAmp, double primaryResonance, double primaryMass,
+            byte secondaryImpactMaterial, double secondaryAmp, double secondaryResonance, double secondaryMass,
+            double speed, byte audioEventType, byte scrapeMaterial, double duration, bool hasSeed, int seed,
+            double simulationAmp, double scrapeMaxSpeed, bool preventDistortion, bool clampContactTime, int framerate, out byte[] audio)
+        {
+            ExternalAudioParameters parameters = new ExternalAudioParameters
+            {
+                primaryImpactMaterial = primaryImpactMaterial,
+                primaryAmp = primaryAmp,
+                primaryResonance = primaryResonance,
+                primaryMass = primaryMass,
+                secondaryImpactMaterial = secondaryImpactMaterial,
+                secondaryAmp = secondaryAmp,
+                secondaryResonance = secondaryResonance,
+                secondaryMass = secondaryMass,
+                speed = speed,
+                audioEventType = audioEventType,
+                scrapeMaterial = scrapeMaterial,
+                duration = duration,
+                hasSeed = hasSeed,
+                seed = seed,
+                simulationAmp = simulationAmp,
+                scrapeMaxSpeed = scrapeMaxSpeed,
+                preventDistortion = preventDistortion,
+                clampContactTime = clampContactTime,
+                framerate = framerate
+            };
+            return TryGetAudio(parameters, out audio);
+        }
+
+
         /// <summary>
         /// Load and return an impact material.
         /// </summary>
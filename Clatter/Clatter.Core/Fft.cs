@@ -0,0 +1,93 @@
+﻿using System;
+
+
+namespace Clatter.Core
+{
+    /// <summary>
+    /// An in-place radix-2 Fast Fourier Transform. Clatter uses this to convolve long arrays (see: DoubleExtensions.ConvolveFft()).
+    ///
+    /// Source: https://en.wikipedia.org/wiki/Cooley%E2%80%93Tukey_FFT_algorithm#Data_reordering,_bit_reversal,_and_in-place_algorithms
+    /// </summary>
+    public static class Fft
+    {
+        /// <summary>
+        /// Apply a Fast Fourier Transform to complex data in-place.
+        /// </summary>
+        /// <param name="real">The real components. This array will be modified.</param>
+        /// <param name="imaginary">The imaginary components. This array will be modified.</param>
+        /// <param name="n">The number of values to transform. This must be a power of two and can be less than the true length of the arrays.</param>
+        /// <param name="inverse">If true, apply an inverse transform. The output is scaled by 1/n.</param>
+        public static void Transform(double[] real, double[] imaginary, int n, bool inverse)
+        {
+            // Reorder the data by bit-reversing the indices.
+            int j = 0;
+            double t;
+            for (int i = 1; i < n; i++)
+            {
+                int bit = n >> 1;
+                while ((j & bit) != 0)
+                {
+                    j ^= bit;
+                    bit >>= 1;
+                }
+                j ^= bit;
+                if (i < j)
+                {
+                    t = real[i];
+                    real[i] = real[j];
+                    real[j] = t;
+                    t = imaginary[i];
+                    imaginary[i] = imaginary[j];
+                    imaginary[j] = t;
+                }
+            }
+            // Combine the butterflies.
+            double sign = inverse ? 1 : -1;
+            for (int size = 2; size <= n; size <<= 1)
+            {
+                int half = size >> 1;
+                double theta = sign * 2 * Math.PI / size;
+                for (int k = 0; k < half; k++)
+                {
+                    double wr = Math.Cos(theta * k);
+                    double wi = Math.Sin(theta * k);
+                    for (int a = k; a < n; a += size)
+                    {
+                        int b = a + half;
+                        double tr = wr * real[b] - wi * imaginary[b];
+                        double ti = wr * imaginary[b] + wi * real[b];
+                        real[b] = real[a] - tr;
+                        imaginary[b] = imaginary[a] - ti;
+                        real[a] += tr;
+                        imaginary[a] += ti;
+                    }
+                }
+            }
+            // Scale the inverse transform.
+            if (inverse)
+            {
+                double scale = 1.0 / n;
+                for (int i = 0; i < n; i++)
+                {
+                    real[i] *= scale;
+                    imaginary[i] *= scale;
+                }
+            }
+        }
+
+
+        /// <summary>
+        /// Returns the smallest power of two that is greater than or equal to length.
+        /// </summary>
+        /// <param name="length">The length of the data.</param>
+        public static int GetSize(int length)
+        {
+            int n = 1;
+            while (n < length)
+            {
+                n <<= 1;
+            }
+            return n;
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,58 @@
+﻿using System;
+
+
+namespace Clatter.Core
+{
+    /// <summary>
+    /// Extensions for float arrays. Use these to process audio with half of the memory of double arrays, for example when rendering many sounds offline. Float precision is more than adequate for audio playback, but Clatter's synthesis uses doubles internally.
+    /// </summary>
+    public static class FloatExtensions
+    {
+        /// <summary>
+        /// Convolve an array with the given kernel. This is equivalent to `DoubleExtensions.Convolve()` except that the arrays are floats. The sums are accumulated as doubles to limit rounding error.
+        /// </summary>
+        /// <param name="a">(this)</param>
+        /// <param name="kernel">A convolution kernel.</param>
+        /// <param name="length">The length of the convolved array.</param>
+        /// <param name="result">The output array. This will be resized if needed.</param>
+        public static void Convolve(this float[] a, float[] kernel, int length, ref float[] result)
+        {
+            if (result.Length < length)
+            {
+                Array.Resize(ref result, length * 2);
+            }
+            double sum;
+            int n1;
+            int n2;
+            int inputLength = a.Length;
+            int kernelLength = kernel.Length;
+            for (int i = length - 1; i >= 0; i--)
+            {
+                sum = 0;
+                n1 = i < inputLength ? 0 : i - inputLength + 1;
+                n2 = i < kernelLength ? i : kernelLength - 1;
+                for (int j = n1; j <= n2; j++)
+                {
+                    sum += (double)a[i - j] * kernel[j];
+                }
+                result[i] = (float)sum;
+            }
+        }
+
+
+        /// <summary>
+        /// Returns this array converted to doubles.
+        /// </summary>
+        /// <param name="a">(this)</param>
+        /// <param name="length">The length of the converted array.</param>
+        public static double[] ToDoubles(this float[] a, int length)
+        {
+            double[] ds = new double[length];
+            for (int i = 0; i < length; i++)
+            {
+                ds[i] = a[i];
+            }
+            return ds;
+        }
+    }
+}
\ No newline at end of file
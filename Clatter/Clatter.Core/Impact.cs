@@ -1,5 +1,7 @@
 ﻿using System;
 using System.Diagnostics;
+using System.Runtime.ExceptionServices;
+using System.Threading;
 
 
 namespace Clatter.Core
@@ -50,6 +52,16 @@ namespace Clatter.Core
         // ReSharper disable once NotAccessedField.Local
         private double[] impulseResponse = new double[9000];
         /// <summary>
+        /// The cached impulse response array used by Synthesize().
+        /// </summary>
+        [ThreadStatic]
+        private static double[] synthesisImpulseResponse;
+        /// <summary>
+        /// If true, we've set the synthesisImpulseResponse array on this thread.
+        /// </summary>
+        [ThreadStatic]
+        private static bool setSynthesisImpulseResponse;
+        /// <summary>
         /// The stopwatch used to record time.
         /// </summary>
         private readonly Stopwatch watch = new Stopwatch();
@@ -105,50 +117,252 @@ namespace Clatter.Core
                 {
                     maxT = Math.Min(maxT, MAX_CONTACT_TIME);
                 }
-                // Convolve with force, with contact time scaled by the object mass.
-                double[] frc = LinSpace.Get(0, Math.PI, (int)Math.Ceiling(maxT * Globals.framerate));
-                // Clamp the amp.
-                if (preventDistortion && amp > MAX_AMP)
+                // Convolve with the contact force and scale by the amp.
+                ApplyContact(impulseResponse, impulseResponseLength, maxT, amp, ref samples.samples);
+                samples.length = impulseResponseLength;
+                // Restart the clock.
+                watch.Restart();
+                // Update the collision count.
+                collisionCount++;
+                return true;
+            }
+        }
+
+        
+        /// <summary>
+        /// Synthesize impact audio from arbitrary modes in a single call. Returns the length of the audio data.
+        ///
+        /// This is the same process that GetAudio() uses: the modes are summed into an impulse response, which is then convolved with a sinusoidal contact force and scaled by the amp. Unlike GetAudio(), this doesn't require `ClatterObjectData` or randomly adjust the modes between successive impacts. To synthesize the modes separately, see: `Modes.Sum(frequencies, powers, decayTimes, resonance, synthSound)`.
+        /// </summary>
+        /// <param name="frequencies">Mode frequencies in Hz.</param>
+        /// <param name="powers">Mode onset powers in dB.</param>
+        /// <param name="decayTimes">Mode decay times i.e. the time in ms it takes for each mode to decay 60dB from its onset power.</param>
+        /// <param name="resonance">The object's audio resonance value. This affects the decay times of the modes.</param>
+        /// <param name="contactTime">The contact time in seconds. This isn't affected by clampContactTime.</param>
+        /// <param name="amp">The amplitude of the audio. If preventDistortion == true, this is clamped to 0.99.</param>
+        /// <param name="samples">The audio samples. This will be resized if needed.</param>
+        public static int Synthesize(double[] frequencies, double[] powers, double[] decayTimes, double resonance, double contactTime, double amp, ref double[] samples)
+        {
+            if (amp <= 0)
+            {
+                return 0;
+            }
+            if (!setSynthesisImpulseResponse)
+            {
+                setSynthesisImpulseResponse = true;
+                synthesisImpulseResponse = new double[Globals.DEFAULT_SAMPLES_LENGTH];
+            }
+            int impulseResponseLength = Modes.Sum(frequencies, powers, decayTimes, resonance, ref synthesisImpulseResponse);
+            if (impulseResponseLength == 0)
+            {
+                return 0;
+            }
+            ApplyContact(synthesisImpulseResponse, impulseResponseLength, contactTime, amp, ref samples);
+            return impulseResponseLength;
+        }
+
+
+        /// <summary>
+        /// Synthesize many impacts in parallel, for example to generate a large bank of sounds offline. Each impact is synthesized with Synthesize(). Returns the audio of each impact.
+        ///
+        /// If an exception is thrown while synthesizing an impact or invoking progress, the remaining impacts aren't synthesized and the first exception is rethrown on the calling thread.
+        /// </summary>
+        /// <param name="parameters">The parameters of each impact.</param>
+        /// <param name="progress">If not null, this is invoked after each impact is synthesized. The parameters are the number of impacts that have been synthesized so far and the total number of impacts. Invocations are serialized, meaning that this is never invoked by two threads at the same time, and the count always increases.</param>
+        /// <param name="maxThreads">The maximum number of threads. If this is less than 1, Environment.ProcessorCount is used.</param>
+        public static Samples[] SynthesizeBank(ImpactParameters[] parameters, Action<int, int> progress = null, int maxThreads = 0)
+        {
+            int count = parameters.Length;
+            Samples[] bank = new Samples[count];
+            int numThreads = Math.Max(Math.Min(maxThreads < 1 ? Environment.ProcessorCount : maxThreads, count), 1);
+            int completed = 0;
+            object progressLock = new object();
+            ExceptionDispatchInfo exception = null;
+            object exceptionLock = new object();
+            Thread[] threads = new Thread[numThreads];
+            for (int i = 0; i < numThreads; i++)
+            {
+                // Get a thread-safe index.
+                int start = i;
+                threads[i] = new Thread(() =>
                 {
-                    amp = MAX_AMP;
+                    try
+                    {
+                        // Each thread synthesizes every numThreads-th impact. Stop if another thread threw an exception.
+                        for (int j = start; j < count && exception == null; j += numThreads)
+                        {
+                            ImpactParameters p = parameters[j];
+                            Samples s = new Samples(Globals.DEFAULT_SAMPLES_LENGTH);
+                            s.length = Synthesize(p.frequencies, p.powers, p.decayTimes, p.resonance, p.contactTime, p.amp, ref s.samples);
+                            bank[j] = s;
+                            if (progress != null)
+                            {
+                                lock (progressLock)
+                                {
+                                    completed++;
+                                    progress(completed, count);
+                                }
+                            }
+                        }
+                    }
+                    catch (Exception e)
+                    {
+                        // Store the first exception so that it can be rethrown on the calling thread.
+                        lock (exceptionLock)
+                        {
+                            if (exception == null)
+                            {
+                                exception = ExceptionDispatchInfo.Capture(e);
+                            }
+                        }
+                    }
+                });
+                threads[i].Start();
+            }
+            // Wait for the threads to finish.
+            for (int i = 0; i < numThreads; i++)
+            {
+                threads[i].Join();
+            }
+            if (exception != null)
+            {
+                exception.Throw();
+            }
+            return bank;
+        }
+
+
+        /// <summary>
+        /// Returns a mode power in dB derived from a collision velocity, for example to set the powers of modes passed to Synthesize(). Velocities at or below minVelocity map to minPower and velocities at or above maxVelocity map to maxPower.
+        /// </summary>
+        /// <param name="velocity">The collision velocity in meters per second.</param>
+        /// <param name="minVelocity">The minimum velocity in meters per second. If logarithmic == true, this must be greater than 0.</param>
+        /// <param name="maxVelocity">The maximum velocity in meters per second. This must be greater than minVelocity.</param>
+        /// <param name="minPower">The power in dB at the minimum velocity.</param>
+        /// <param name="maxPower">The power in dB at the maximum velocity.</param>
+        /// <param name="logarithmic">If true, the power is proportional to the logarithm of the velocity, meaning that the loudness changes more at low velocities. If false, the power is proportional to the velocity.</param>
+        public static double PowerFromVelocity(double velocity, double minVelocity, double maxVelocity, double minPower, double maxPower, bool logarithmic = false)
+        {
+            if (!(maxVelocity > minVelocity) || (logarithmic && !(minVelocity > 0)))
+            {
+                throw new Exception("Invalid velocity range: " + minVelocity + ", " + maxVelocity);
+            }
+            double t;
+            if (velocity <= minVelocity)
+            {
+                t = 0;
+            }
+            else if (velocity >= maxVelocity)
+            {
+                t = 1;
+            }
+            else if (logarithmic)
+            {
+                t = Math.Log(velocity / minVelocity) / Math.Log(maxVelocity / minVelocity);
+            }
+            else
+            {
+                t = (velocity - minVelocity) / (maxVelocity - minVelocity);
+            }
+            return minPower + t * (maxPower - minPower);
+        }
+
+
+        /// <summary>
+        /// Synthesize the same impact at each of a series of resonance values and concatenate the results, separated by silent gaps. Returns the length of the audio data.
+        ///
+        /// This is meant to be used when authoring a material, to hear how resonance affects the decay of its modes. Each impact is synthesized with Synthesize() and is therefore normalized to the same amp, so the impacts differ only in their decay times.
+        /// </summary>
+        /// <param name="frequencies">Mode frequencies in Hz.</param>
+        /// <param name="powers">Mode onset powers in dB.</param>
+        /// <param name="decayTimes">Mode decay times i.e. the time in ms it takes for each mode to decay 60dB from its onset power.</param>
+        /// <param name="resonances">The resonance value of each impact.</param>
+        /// <param name="contactTime">The contact time in seconds.</param>
+        /// <param name="amp">The amplitude of each impact.</param>
+        /// <param name="gapLength">The length of the silence between successive impacts in samples.</param>
+        /// <param name="samples">The audio samples. This will be resized if needed.</param>
+        public static int SynthesizeSweep(double[] frequencies, double[] powers, double[] decayTimes, double[] resonances, double contactTime, double amp, int gapLength, ref double[] samples)
+        {
+            if (gapLength < 0)
+            {
+                throw new Exception("Invalid gap length: " + gapLength);
+            }
+            double[] impact = new double[Globals.DEFAULT_SAMPLES_LENGTH];
+            int length = 0;
+            for (int i = 0; i < resonances.Length; i++)
+            {
+                int impactLength = Synthesize(frequencies, powers, decayTimes, resonances[i], contactTime, amp, ref impact);
+                // Add a gap before each impact except the first.
+                int start = i == 0 ? 0 : length + gapLength;
+                if (samples.Length < start + impactLength)
+                {
+                    Array.Resize(ref samples, (start + impactLength) * 2);
                 }
+                Array.Clear(samples, length, start - length);
+                Buffer.BlockCopy(impact, 0, samples, start * 8, impactLength * 8);
+                length = start + impactLength;
+            }
+            return length;
+        }
+
+
+        /// <summary>
+        /// Convolve an impulse response with a sinusoidal contact force, normalize the result, and scale it by the amp.
+        /// </summary>
+        /// <param name="impulseResponse">The impulse response.</param>
+        /// <param name="impulseResponseLength">The length of the impulse response. The audio data will have the same length.</param>
+        /// <param name="maxT">The contact time in seconds.</param>
+        /// <param name="amp">The amplitude of the audio.</param>
+        /// <param name="samples">The audio samples. This will be resized if needed.</param>
+        private static void ApplyContact(double[] impulseResponse, int impulseResponseLength, double maxT, double amp, ref double[] samples)
+        {
+            // Clamp the amp.
+            if (preventDistortion && amp > MAX_AMP)
+            {
+                amp = MAX_AMP;
+            }
+            // Convolve with force, with contact time scaled by the object mass.
+            int contactLength = (int)Math.Ceiling(maxT * Globals.framerate);
+            double[] frc;
+            // A contact that is shorter than two samples is an impulse.
+            if (contactLength <= 1)
+            {
+                frc = new double[] { 1 };
+            }
+            else
+            {
+                frc = LinSpace.Get(0, Math.PI, contactLength);
                 for (int i = 0; i < frc.Length; i++)
                 {
                     frc[i] = Math.Sin(frc[i]);
                 }
-                // Convolve.
-                impulseResponse.Convolve(frc, impulseResponseLength, ref samples.samples);
-                double maxSample = 0;
-                for (int i = 0; i < impulseResponseLength; i++)
-                {
-                    if (samples.samples[i] > maxSample)
-                    {
-                        maxSample = samples.samples[i];
-                    }
-                }
-                maxSample = Math.Abs(maxSample);
-                double maxAbsSample = 0;
-                double abs;
-                for (int i = 0; i < impulseResponseLength; i++)
+            }
+            // Convolve.
+            impulseResponse.Convolve(frc, impulseResponseLength, ref samples);
+            double maxSample = 0;
+            for (int i = 0; i < impulseResponseLength; i++)
+            {
+                if (samples[i] > maxSample)
                 {
-                    samples.samples[i] /= maxSample;
-                    abs = Math.Abs(samples.samples[i]);
-                    if (abs > maxAbsSample)
-                    {
-                        maxAbsSample = abs;
-                    }
+                    maxSample = samples[i];
                 }
-                // Scale by the amp value.
-                for (int i = 0; i < impulseResponseLength; i++)
+            }
+            maxSample = Math.Abs(maxSample);
+            double maxAbsSample = 0;
+            double abs;
+            for (int i = 0; i < impulseResponseLength; i++)
+            {
+                samples[i] /= maxSample;
+                abs = Math.Abs(samples[i]);
+                if (abs > maxAbsSample)
                 {
-                    samples.samples[i] = amp * samples.samples[i] / maxAbsSample;
+                    maxAbsSample = abs;
                 }
-                samples.length = impulseResponseLength;
-                // Restart the clock.
-                watch.Restart();
-                // Update the collision count.
-                collisionCount++;
-                return true;
+            }
+            // Scale by the amp value.
+            for (int i = 0; i < impulseResponseLength; i++)
+            {
+                samples[i] = amp * samples[i] / maxAbsSample;
             }
         }
 
@@ -33,9 +33,15 @@ namespace Clatter.Core
         public double[] rt;
         /// <summary>
         /// A dictionary of impact data. Key = An ImpactMaterial value. Value = An ImpactMaterialData.
+        ///
+        /// This dictionary can be safely read by multiple threads at once, but not while another thread is calling Load() with a material that hasn't been loaded yet. In a multi-threaded context, load every material before generating audio.
         /// </summary>
         public static Dictionary<ImpactMaterial, ImpactMaterialData> impactMaterials = new Dictionary<ImpactMaterial, ImpactMaterialData>();
         /// <summary>
+        /// A lock for loading data into impactMaterials.
+        /// </summary>
+        private static readonly object ImpactMaterialsLock = new object();
+        /// <summary>
         /// A dictionary of density values per impact material. Key = An ImpactMaterialUnsized. Value = Density in kg/m^3.
         /// </summary>
         public static readonly Dictionary<ImpactMaterialUnsized, int> Density = new Dictionary<ImpactMaterialUnsized, int>()
@@ -62,33 +68,36 @@ namespace Clatter.Core
 
 
         /// <summary>
-        /// Load impact material data from a file relative to this assembly.
+        /// Load impact material data from a file relative to this assembly. This can be safely called by multiple threads at once.
         /// </summary>
         /// <param name="impactMaterial">The impact material.</param>
         public static void Load(ImpactMaterial impactMaterial)
         {
-            // We already loaded the material.
-            if (impactMaterials.ContainsKey(impactMaterial))
+            lock (ImpactMaterialsLock)
             {
-                return;
+                // We already loaded the material.
+                if (impactMaterials.ContainsKey(impactMaterial))
+                {
+                    return;
+                }
+                // Load the raw byte data.
+                byte[] raw = Loader.Load("ImpactMaterials." + impactMaterial + "_mm.bytes");
+                // The first 12 bytes are the lengths of the arrays.
+                double[] cf = new double[BitConverter.ToInt32(raw, 0)];
+                double[] op = new double[BitConverter.ToInt32(raw, 4)];
+                double[] rt = new double[BitConverter.ToInt32(raw, 8)];
+                // Copy the data into the arrays.
+                Buffer.BlockCopy(raw, 12, cf, 0, cf.Length * 8);
+                Buffer.BlockCopy(raw, 12 + cf.Length * 8, op, 0, op.Length * 8);
+                Buffer.BlockCopy(raw, 12 + cf.Length * 8 + op.Length * 8, rt, 0, rt.Length * 8);
+                // Deserialize the material data from the JSON text.
+                impactMaterials.Add(impactMaterial, new ImpactMaterialData()
+                {
+                    cf = cf,
+                    op = op,
+                    rt = rt
+                });
             }
-            // Load the raw byte data.
-            byte[] raw = Loader.Load("ImpactMaterials." + impactMaterial + "_mm.bytes");
-            // The first 12 bytes are the lengths of the arrays.
-            double[] cf = new double[BitConverter.ToInt32(raw, 0)];
-            double[] op = new double[BitConverter.ToInt32(raw, 4)];
-            double[] rt = new double[BitConverter.ToInt32(raw, 8)];
-            // Copy the data into the arrays.
-            Buffer.BlockCopy(raw, 12, cf, 0, cf.Length * 8);
-            Buffer.BlockCopy(raw, 12 + cf.Length * 8, op, 0, op.Length * 8);
-            Buffer.BlockCopy(raw, 12 + cf.Length * 8 + op.Length * 8, rt, 0, rt.Length * 8);
-            // Deserialize the material data from the JSON text.
-            impactMaterials.Add(impactMaterial, new ImpactMaterialData()
-            {
-                cf = cf,
-                op = op,
-                rt = rt
-            });
         }
         
         
@@ -0,0 +1,53 @@
+﻿namespace Clatter.Core
+{
+    /// <summary>
+    /// The parameters of an impact that is synthesized from arbitrary modes. See: Impact.Synthesize() and Impact.SynthesizeBank().
+    /// </summary>
+    public class ImpactParameters
+    {
+        /// <summary>
+        /// Mode frequencies in Hz.
+        /// </summary>
+        public readonly double[] frequencies;
+        /// <summary>
+        /// Mode onset powers in dB.
+        /// </summary>
+        public readonly double[] powers;
+        /// <summary>
+        /// Mode decay times i.e. the time in ms it takes for each mode to decay 60dB from its onset power.
+        /// </summary>
+        public readonly double[] decayTimes;
+        /// <summary>
+        /// The object's audio resonance value. This affects the decay times of the modes.
+        /// </summary>
+        public readonly double resonance;
+        /// <summary>
+        /// The contact time in seconds.
+        /// </summary>
+        public readonly double contactTime;
+        /// <summary>
+        /// The amplitude of the audio.
+        /// </summary>
+        public readonly double amp;
+
+
+        /// <summary>
+        /// (constructor)
+        /// </summary>
+        /// <param name="frequencies">Mode frequencies in Hz.</param>
+        /// <param name="powers">Mode onset powers in dB.</param>
+        /// <param name="decayTimes">Mode decay times i.e. the time in ms it takes for each mode to decay 60dB from its onset power.</param>
+        /// <param name="resonance">The object's audio resonance value. This affects the decay times of the modes.</param>
+        /// <param name="contactTime">The contact time in seconds.</param>
+        /// <param name="amp">The amplitude of the audio.</param>
+        public ImpactParameters(double[] frequencies, double[] powers, double[] decayTimes, double resonance, double contactTime, double amp)
+        {
+            this.frequencies = frequencies;
+            this.powers = powers;
+            this.decayTimes = decayTimes;
+            this.resonance = resonance;
+            this.contactTime = contactTime;
+            this.amp = amp;
+        }
+    }
+}
\ No newline at end of file
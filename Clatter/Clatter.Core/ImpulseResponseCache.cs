@@ -0,0 +1,69 @@
+﻿using System;
+using System.Collections.Generic;
+
+
+namespace Clatter.Core
+{
+    /// <summary>
+    /// A cache of impulse responses keyed by an ID. Register an impulse response once, and then use it for any number of scrapes without copying it or generating it from modes again (see: Scrape.SetImpulseResponse()).
+    ///
+    /// Scrapes share the cached array rather than copying it. Unregistering an impulse response removes it from the cache, but scrapes that already use it will continue to do so.
+    ///
+    /// This class is thread-safe.
+    /// </summary>
+    public static class ImpulseResponseCache
+    {
+        /// <summary>
+        /// The cached impulse responses. Key = The ID.
+        /// </summary>
+        private static readonly Dictionary<uint, double[]> ImpulseResponses = new Dictionary<uint, double[]>();
+
+
+        /// <summary>
+        /// Register an impulse response. If an impulse response with this ID is already registered, it is replaced.
+        /// </summary>
+        /// <param name="id">The ID of the impulse response.</param>
+        /// <param name="impulseResponse">The impulse response. This array is copied.</param>
+        /// <param name="length">The length of the impulse response (can be less than the true length of the array).</param>
+        public static void Register(uint id, double[] impulseResponse, int length)
+        {
+            if (length <= 0)
+            {
+                throw new Exception("Invalid impulse response length: " + length);
+            }
+            double[] ir = new double[length];
+            Buffer.BlockCopy(impulseResponse, 0, ir, 0, length * 8);
+            lock (ImpulseResponses)
+            {
+                ImpulseResponses[id] = ir;
+            }
+        }
+
+
+        /// <summary>
+        /// Unregister an impulse response. Returns true if an impulse response with this ID was registered.
+        /// </summary>
+        /// <param name="id">The ID of the impulse response.</param>
+        public static bool Unregister(uint id)
+        {
+            lock (ImpulseResponses)
+            {
+                return ImpulseResponses.Remove(id);
+            }
+        }
+
+
+        /// <summary>
+        /// Try to get a registered impulse response. Returns true if an impulse response with this ID is registered. The array must not be modified.
+        /// </summary>
+        /// <param name="id">The ID of the impulse response.</param>
+        /// <param name="impulseResponse">The impulse response.</param>
+        public static bool TryGet(uint id, out double[] impulseResponse)
+        {
+            lock (ImpulseResponses)
+            {
+                return ImpulseResponses.TryGetValue(id, out impulseResponse);
+            }
+        }
+    }
+}
\ No newline at end of file
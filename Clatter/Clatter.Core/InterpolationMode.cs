@@ -0,0 +1,17 @@
+﻿namespace Clatter.Core
+{
+    /// <summary>
+    /// The interpolation function used to sample the scrape surface. See: Scrape.interpolationMode.
+    /// </summary>
+    public enum InterpolationMode : byte
+    {
+        /// <summary>
+        /// Piece-wise linear interpolation. See: DoubleExtensions.Interpolate1D().
+        /// </summary>
+        linear = 0,
+        /// <summary>
+        /// Catmull-Rom cubic interpolation. See: DoubleExtensions.Interpolate1DCubic().
+        /// </summary>
+        cubic = 1
+    }
+}
\ No newline at end of file
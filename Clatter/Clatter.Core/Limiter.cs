@@ -0,0 +1,111 @@
+﻿using System;
+
+
+namespace Clatter.Core
+{
+    /// <summary>
+    /// A limiter that smoothly reduces the gain of audio whose absolute value exceeds a threshold. This can prevent mixed audio from exceeding 0 dBFS when many impacts coincide. Unlike Dynamics.SoftClip(), a Limiter doesn't change the shape of the waveform; instead, the gain is reduced over the attack time and restored over the release time.
+    /// </summary>
+    public class Limiter
+    {
+        /// <summary>
+        /// The threshold (0 to 1).
+        /// </summary>
+        private readonly double threshold;
+        /// <summary>
+        /// The per-sample smoothing coefficient while the gain is being reduced.
+        /// </summary>
+        private readonly double attackCoefficient;
+        /// <summary>
+        /// The per-sample smoothing coefficient while the gain is being restored.
+        /// </summary>
+        private readonly double releaseCoefficient;
+        /// <summary>
+        /// The current gain.
+        /// </summary>
+        private double gain = 1;
+
+
+        /// <summary>
+        /// (constructor)
+        /// </summary>
+        /// <param name="threshold">The threshold (0 to 1). The gain is reduced such that the absolute value of each sample approaches this value.</param>
+        /// <param name="attack">The attack time in milliseconds i.e. the time it takes to reduce the gain. If this is 0, the gain is reduced immediately.</param>
+        /// <param name="release">The release time in milliseconds i.e. the time it takes to restore the gain. If this is 0, the gain is restored immediately.</param>
+        public Limiter(double threshold, double attack, double release)
+        {
+            if (!(threshold > 0))
+            {
+                throw new Exception("Invalid limiter threshold: " + threshold);
+            }
+            if (attack < 0 || release < 0)
+            {
+                throw new Exception("Invalid limiter attack and/or release: " + attack + ", " + release);
+            }
+            this.threshold = threshold;
+            attackCoefficient = GetCoefficient(attack);
+            releaseCoefficient = GetCoefficient(release);
+        }
+
+
+        /// <summary>
+        /// Limit a single sample.
+        /// </summary>
+        /// <param name="sample">The sample.</param>
+        public double ProcessSample(double sample)
+        {
+            double abs = Math.Abs(sample);
+            double targetGain = abs > threshold ? threshold / abs : 1;
+            // Smooth the gain towards the target gain.
+            double coefficient = targetGain < gain ? attackCoefficient : releaseCoefficient;
+            gain = coefficient * gain + (1 - coefficient) * targetGain;
+            return sample * gain;
+        }
+
+
+        /// <summary>
+        /// Limit audio samples in-place.
+        /// </summary>
+        /// <param name="samples">The audio samples. This array will be modified.</param>
+        /// <param name="length">The length of the audio data.</param>
+        public void Process(double[] samples, int length)
+        {
+            for (int i = 0; i < length; i++)
+            {
+                samples[i] = ProcessSample(samples[i]);
+            }
+        }
+
+
+        /// <summary>
+        /// Returns the current gain (0 to 1). This is less than 1 if the gain is being reduced.
+        /// </summary>
+        public double GetGain()
+        {
+            return gain;
+        }
+
+
+        /// <summary>
+        /// Clear the limiter's state.
+        /// </summary>
+        public void Reset()
+        {
+            gain = 1;
+        }
+
+
+        /// <summary>
+        /// Returns a per-sample one-pole smoothing coefficient.
+        /// </summary>
+        /// <param name="time">The time constant in milliseconds.</param>
+        private static double GetCoefficient(double time)
+        {
+            if (time <= 0)
+            {
+                return 0;
+            }
+            return Math.Exp(-1 / (time / 1e3 * Globals.framerateD));
+        }
+    }
+}
\ No newline at end of file
@@ -27,20 +27,29 @@ namespace Clatter.Core
         /// </summary>
         /// <param name="a">The start value.</param>
         /// <param name="b">The end value. It's assumed that b is greater than a.</param>
-        /// <param name="steps">The number of steps.</param>
+        /// <param name="steps">The number of steps. If this is 0, the array isn't modified. If this is 1, the array will start with a (like numpy.linspace).</param>
         /// <param name="arr">The array. This will be resized if needed.</param>
         public static void GetInPlace(double a, double b, int steps, ref double[] arr)
         {
+            if (steps <= 0)
+            {
+                return;
+            }
             if (arr.Length < steps)
             {
                 Array.Resize(ref arr, steps * 2);
             }
+            if (steps == 1)
+            {
+                arr[0] = a;
+                return;
+            }
             double stepSize = (b - a) / (steps - 1);
-            for (uint i = 0; i < arr.Length - 1; i++)
+            for (int i = 0; i < steps - 1; i++)
             {
                 arr[i] = a + i * stepSize;
             }
-            arr[arr.Length - 1] = b;
+            arr[steps - 1] = b;
         }
     }
 }
\ No newline at end of file
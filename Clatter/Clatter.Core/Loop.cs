@@ -0,0 +1,62 @@
+﻿using System;
+
+
+namespace Clatter.Core
+{
+    /// <summary>
+    /// Prepare audio samples to be played as a seamless loop, for example to sustain a scrape during continuous contact.
+    ///
+    /// A loop of length n plays samples 0 to n - 1 and then jumps back to sample 0. The seam is click-free if sample n - 1 flows smoothly into sample 0, i.e. if sample 0 has the same value and slope as sample n.
+    /// </summary>
+    public static class Loop
+    {
+        /// <summary>
+        /// Returns the loop length that best matches the value and slope of the start of the samples. The seam of a loop with the returned length will have the smallest possible discontinuity. If there are multiple equally good loop lengths, this returns the longest.
+        /// </summary>
+        /// <param name="samples">The audio samples.</param>
+        /// <param name="length">The length of the audio data (can be less than the true length of the array).</param>
+        /// <param name="minLength">The minimum loop length. If this is greater than or equal to length - 1, this returns length.</param>
+        public static int FindLoopLength(double[] samples, int length, int minLength)
+        {
+            minLength = Math.Max(minLength, 1);
+            if (minLength >= length - 1)
+            {
+                return length;
+            }
+            double startSlope = samples[1] - samples[0];
+            double bestCost = double.PositiveInfinity;
+            int bestLength = length;
+            double cost;
+            // Sample n must exist, so the longest possible loop is length - 1.
+            for (int n = length - 1; n >= minLength; n--)
+            {
+                cost = Math.Abs(samples[n] - samples[0]) + Math.Abs(samples[n] - samples[n - 1] - startSlope);
+                if (cost < bestCost)
+                {
+                    bestCost = cost;
+                    bestLength = n;
+                }
+            }
+            return bestLength;
+        }
+
+
+        /// <summary>
+        /// Crossfade the start of the samples with the samples that follow the end of the loop, so that the loop seam is continuous. After this, sample 0 equals the former sample loopLength.
+        /// </summary>
+        /// <param name="samples">The audio samples. This array will be modified.</param>
+        /// <param name="length">The length of the audio data (can be less than the true length of the array).</param>
+        /// <param name="loopLength">The length of the loop. See: FindLoopLength().</param>
+        /// <param name="crossfadeLength">The length of the crossfade in samples. This is clamped to the number of samples after the end of the loop and to the loop length.</param>
+        public static void PrepareLoop(double[] samples, int length, int loopLength, int crossfadeLength)
+        {
+            crossfadeLength = Math.Max(Math.Min(crossfadeLength, Math.Min(length - loopLength, loopLength)), 0);
+            double angle;
+            for (int i = 0; i < crossfadeLength; i++)
+            {
+                angle = (double)i / crossfadeLength * Math.PI / 2;
+                samples[i] = samples[loopLength + i] * Math.Cos(angle) + samples[i] * Math.Sin(angle);
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -9,16 +9,32 @@ namespace Clatter.Core
     ///
     /// Median-Filters perform well for de-noise applications where it's important to not loose sharp steps/edges.
     ///
+    /// An adaptive Median-Filter narrows its window when it detects a transient (a derivative much larger than the recent mean derivative), and then widens the window back to its full size one sample at a time. This preserves transients while still smoothing noise between them.
+    ///
     /// This is an optimized version of this class: https://github.com/mathnet/mathnet-filtering/blob/master/src/Filtering/Median/OnlineMedianFilter.cs
     /// </summary>
     public class MedianFilter
     {
+        /// <summary>
+        /// The default window size.
+        /// </summary>
+        public const int DEFAULT_WINDOW_SIZE = 5;
+        /// <summary>
+        /// The default transient threshold of an adaptive filter.
+        /// </summary>
+        public const double DEFAULT_TRANSIENT_THRESHOLD = 4;
+        /// <summary>
+        /// The smoothing coefficient of the mean derivative of an adaptive filter.
+        /// </summary>
+        private const double DERIVATIVE_SMOOTHING = 0.01;
+        
+        
         /// <summary>
         /// The filter buffer.
         /// </summary>
         private readonly double[] buffer;
         /// <summary>
-        /// A dictionary of cached offset buffers. Key = The length of the buffer.
+        /// A dictionary of cached offset buffers. Key = The length of the buffer. The median is calculated from a copy of the filter buffer because MedianInPlace() reorders the array.
         /// </summary>
         private readonly Dictionary<int, double[]> offsetBuffers = new Dictionary<int, double[]>();
         /// <summary>
@@ -29,18 +45,62 @@ namespace Clatter.Core
         /// If true, the buffer is full.
         /// </summary>
         private bool bufferFull;
+        /// <summary>
+        /// If true, this is an adaptive filter.
+        /// </summary>
+        private readonly bool adaptive;
+        /// <summary>
+        /// A derivative greater than this multiple of the mean derivative is a transient.
+        /// </summary>
+        private readonly double transientThreshold;
+        /// <summary>
+        /// The previous sample. This is used only by an adaptive filter.
+        /// </summary>
+        private double previousSample;
+        /// <summary>
+        /// The exponentially smoothed mean of the absolute derivative. This is used only by an adaptive filter.
+        /// </summary>
+        private double meanDerivative;
+        /// <summary>
+        /// The number of finite derivatives that have been measured, up to the window size. An adaptive filter doesn't detect transients until the window size of derivatives have been measured.
+        /// </summary>
+        private int numDerivatives;
+        /// <summary>
+        /// The current window length of an adaptive filter.
+        /// </summary>
+        private int adaptiveWindowLength;
+        
+
+        /// <summary>
+        /// Create a Median Filter with the default window size.
+        /// </summary>
+        public MedianFilter() : this(DEFAULT_WINDOW_SIZE)
+        {
+        }
         
 
         /// <summary>
         /// Create a Median Filter.
         /// </summary>
-        /// <param name="windowSize">The window size.</param>
-        public MedianFilter(int windowSize)
+        /// <param name="windowSize">The window size. Must be at least 1. A larger window will suppress more spikes and a smaller window will preserve more transients.</param>
+        /// <param name="adaptive">If true, narrow the window when a transient is detected.</param>
+        /// <param name="transientThreshold">A derivative greater than this multiple of the mean derivative is a transient. The window is narrowed in proportion to how much the derivative exceeds the threshold. This is used only if adaptive == true. Must be greater than 0.</param>
+        public MedianFilter(int windowSize, bool adaptive = false, double transientThreshold = DEFAULT_TRANSIENT_THRESHOLD)
         {
+            if (windowSize < 1)
+            {
+                throw new Exception("Invalid median filter window size: " + windowSize);
+            }
+            if (!(transientThreshold > 0))
+            {
+                throw new Exception("Invalid median filter transient threshold: " + transientThreshold);
+            }
+            this.adaptive = adaptive;
+            this.transientThreshold = transientThreshold;
             // Set the buffer.
             buffer = new double[windowSize];
             // Generate offset buffers.
-            for (int i = windowSize - 1; i >= 0; i--)
+            for (int i = windowSize; i > 0; i--)
             {
                 offsetBuffers.Add(i, new double[i]);
             }
@@ -48,24 +108,112 @@ namespace Clatter.Core
         
         
         /// <summary>
-        /// Process a single sample.
+        /// Process a single sample. Returns the median of the most recent window size samples.
+        ///
+        /// Until the window is full, this returns the median of all of the samples processed so far: the first sample is returned as-is, and if an even number of samples have been processed, this returns the mean of the two middle values.
         /// </summary>
         /// <param name="sample">The sample.</param>
         public double ProcessSample(double sample)
         {
             buffer[offset = (offset == 0) ? buffer.Length - 1 : offset - 1] = sample;
             bufferFull |= offset == 0;
-            if (bufferFull)
+            int start = bufferFull ? 0 : offset;
+            int length = buffer.Length - start;
+            if (adaptive)
             {
-                return buffer.MedianInPlace();
+                return GetAdaptiveMedian(sample, length);
+            }
+            // Copy to the offset buffer.
+            Buffer.BlockCopy(buffer, start * 8, offsetBuffers[length], 0, length * 8);
+            return offsetBuffers[length].MedianInPlace();
+        }
+
+
+        /// <summary>
+        /// Filter audio samples in-place. The filter's state is retained between calls, meaning that it can filter a continuous stream of audio.
+        /// </summary>
+        /// <param name="samples">The audio samples. This array will be modified.</param>
+        /// <param name="length">The length of the audio data.</param>
+        public void Process(double[] samples, int length)
+        {
+            for (int i = 0; i < length; i++)
+            {
+                samples[i] = ProcessSample(samples[i]);
+            }
+        }
+
+
+        /// <summary>
+        /// Clear the filter's state. Call this before reusing the filter for a new stream.
+        /// </summary>
+        public void Reset()
+        {
+            Array.Clear(buffer, 0, buffer.Length);
+            offset = 0;
+            bufferFull = false;
+            previousSample = 0;
+            meanDerivative = 0;
+            numDerivatives = 0;
+            adaptiveWindowLength = 0;
+        }
+
+
+        /// <summary>
+        /// Returns the median of the most recent samples of an adaptive filter.
+        /// </summary>
+        /// <param name="sample">The most recent sample.</param>
+        /// <param name="length">The number of samples in the buffer.</param>
+        private double GetAdaptiveMedian(double sample, int length)
+        {
+            // The derivative of the first sample is undefined. A non-finite derivative (e.g. of a NaN sample) is ignored so that it can't corrupt the mean derivative.
+            double derivative = Math.Abs(sample - previousSample);
+            bool measured = length > 1 && derivative.IsFinite();
+            previousSample = sample;
+            double limit = transientThreshold * meanDerivative;
+            // Don't detect transients until the mean derivative has warmed up.
+            if (measured && numDerivatives >= buffer.Length && derivative > limit)
+            {
+                // Narrow the window.
+                adaptiveWindowLength = limit > 0 ? (int)(buffer.Length * limit / derivative) : 1;
+                if (adaptiveWindowLength < 1)
+                {
+                    adaptiveWindowLength = 1;
+                }
             }
             else
             {
-                int length = buffer.Length - offset;
-                // Copy to the offset buffer.
-                Buffer.BlockCopy(buffer, offset * 8, offsetBuffers[length], 0, offsetBuffers[length].Length * 8);
-                return offsetBuffers[length].MedianInPlace();
+                // Widen the window.
+                adaptiveWindowLength++;
+            }
+            if (adaptiveWindowLength > length)
+            {
+                adaptiveWindowLength = length;
+            }
+            if (measured)
+            {
+                // Seed the mean derivative with the first derivative.
+                if (numDerivatives == 0)
+                {
+                    meanDerivative = derivative;
+                }
+                else
+                {
+                    meanDerivative += (derivative - meanDerivative) * DERIVATIVE_SMOOTHING;
+                }
+                if (numDerivatives < buffer.Length)
+                {
+                    numDerivatives++;
+                }
+            }
+            // Copy the most recent samples to the offset buffer. The most recent sample is at the offset, followed by progressively older samples.
+            double[] offsetBuffer = offsetBuffers[adaptiveWindowLength];
+            int index = offset;
+            for (int i = 0; i < adaptiveWindowLength; i++)
+            {
+                offsetBuffer[i] = buffer[index];
+                index = index == buffer.Length - 1 ? 0 : index + 1;
             }
+            return offsetBuffer.MedianInPlace();
         }
     }
 }
\ No newline at end of file
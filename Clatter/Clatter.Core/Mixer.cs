@@ -0,0 +1,176 @@
+﻿using System;
+
+
+namespace Clatter.Core
+{
+    /// <summary>
+    /// Mix and route audio samples.
+    /// </summary>
+    public static class Mixer
+    {
+        /// <summary>
+        /// Pan mono audio to stereo using an equal-power pan law. The summed power of the left and right channels is the same for any pan value. At a pan of 0, each channel is -3 dB.
+        /// </summary>
+        /// <param name="mono">The mono audio samples.</param>
+        /// <param name="length">The length of the audio data (can be less than the true length of the array). The left and right channels will have the same length.</param>
+        /// <param name="pan">The pan, from -1 (full left) to 1 (full right). This is clamped to be between -1 and 1.</param>
+        /// <param name="left">The left channel. This will be resized if needed.</param>
+        /// <param name="right">The right channel. This will be resized if needed.</param>
+        public static void Pan(double[] mono, int length, double pan, ref double[] left, ref double[] right)
+        {
+            if (left.Length < length)
+            {
+                Array.Resize(ref left, length * 2);
+            }
+            if (right.Length < length)
+            {
+                Array.Resize(ref right, length * 2);
+            }
+            double angle = (pan.Clamp(-1, 1) + 1) * Math.PI / 4;
+            double leftGain = Math.Cos(angle);
+            double rightGain = Math.Sin(angle);
+            for (int i = 0; i < length; i++)
+            {
+                left[i] = mono[i] * leftGain;
+                right[i] = mono[i] * rightGain;
+            }
+        }
+
+
+        /// <summary>
+        /// Add audio samples, multiplied by a gain, into another array of audio samples starting at an offset. This is useful for summing many concurrent sounds into one output. Samples that would be written past the end of the output data are discarded.
+        /// </summary>
+        /// <param name="output">The output audio samples. This array will be modified.</param>
+        /// <param name="outputLength">The length of the output audio data (can be less than the true length of the array). If offset is greater than or equal to this, the output isn't modified.</param>
+        /// <param name="input">The audio samples to add.</param>
+        /// <param name="inputLength">The length of the audio data to add (can be less than the true length of the array).</param>
+        /// <param name="offset">The index in the output at which to start adding the input.</param>
+        /// <param name="gain">Multiply the input by this value.</param>
+        public static void MixInto(double[] output, int outputLength, double[] input, int inputLength, int offset, double gain)
+        {
+            if (offset < 0)
+            {
+                throw new Exception("Invalid offset: " + offset);
+            }
+            int end = Math.Min(inputLength, outputLength - offset);
+            for (int i = 0; i < end; i++)
+            {
+                output[offset + i] += input[i] * gain;
+            }
+        }
+
+
+        /// <summary>
+        /// Mix a sequence of audio samples, such as the impacts of a bouncing object, into one output array. Each input is multiplied by its gain and added to the output at its offset. Returns the length of the output: the maximum of each offset plus the length of its input.
+        /// </summary>
+        /// <param name="inputs">The audio samples to mix, e.g. impact samples.</param>
+        /// <param name="lengths">The length of each input's audio data. This must have the same length as inputs.</param>
+        /// <param name="offsets">The index in the output at which each input starts. This must have the same length as inputs.</param>
+        /// <param name="gains">Multiply each input by this value. This must have the same length as inputs.</param>
+        /// <param name="output">The output array. This will be resized if needed.</param>
+        public static int Sequence(double[][] inputs, int[] lengths, int[] offsets, double[] gains, ref double[] output)
+        {
+            if (lengths.Length != inputs.Length || offsets.Length != inputs.Length || gains.Length != inputs.Length)
+            {
+                throw new Exception("Inputs, lengths, offsets, and gains have different lengths: " + inputs.Length + ", " + lengths.Length + ", " + offsets.Length + ", " + gains.Length);
+            }
+            int outputLength = 0;
+            for (int i = 0; i < inputs.Length; i++)
+            {
+                if (offsets[i] < 0)
+                {
+                    throw new Exception("Invalid offset: " + offsets[i]);
+                }
+                if (offsets[i] + lengths[i] > outputLength)
+                {
+                    outputLength = offsets[i] + lengths[i];
+                }
+            }
+            if (output.Length < outputLength)
+            {
+                Array.Resize(ref output, outputLength * 2);
+            }
+            Array.Clear(output, 0, outputLength);
+            for (int i = 0; i < inputs.Length; i++)
+            {
+                MixInto(output, outputLength, inputs[i], lengths[i], offsets[i], gains[i]);
+            }
+            return outputLength;
+        }
+
+
+        /// <summary>
+        /// Sum delayed copies of audio samples, each multiplied by a gain, into an output array. This is a cheap way to add a sense of space to audio without convolving it with a full impulse response. To spatialize stereo audio, call this once per channel with different taps. Returns the length of the output: length plus the largest tap.
+        ///
+        /// The input isn't included in the output unless there is a tap of 0.
+        /// </summary>
+        /// <param name="samples">The audio samples.</param>
+        /// <param name="length">The length of the audio data (can be less than the true length of the array).</param>
+        /// <param name="taps">The delay of each reflection in samples.</param>
+        /// <param name="gains">The gain of each reflection. This must have the same length as taps.</param>
+        /// <param name="output">The output array. This must not be samples. This will be resized if needed.</param>
+        public static int ApplyEarlyReflections(double[] samples, int length, int[] taps, double[] gains, ref double[] output)
+        {
+            if (taps.Length != gains.Length)
+            {
+                throw new Exception("Taps and gains have different lengths: " + taps.Length + ", " + gains.Length);
+            }
+            int maxTap = 0;
+            for (int i = 0; i < taps.Length; i++)
+            {
+                if (taps[i] < 0)
+                {
+                    throw new Exception("Invalid tap: " + taps[i]);
+                }
+                if (taps[i] > maxTap)
+                {
+                    maxTap = taps[i];
+                }
+            }
+            int outputLength = length + maxTap;
+            if (output.Length < outputLength)
+            {
+                Array.Resize(ref output, outputLength * 2);
+            }
+            Array.Clear(output, 0, outputLength);
+            for (int i = 0; i < taps.Length; i++)
+            {
+                MixInto(output, outputLength, samples, length, taps[i], gains[i]);
+            }
+            return outputLength;
+        }
+
+
+        /// <summary>
+        /// Crossfade from one array of samples to another, for example from the tail of an impact to the head of a scrape. The end of a overlaps the start of b using equal-power ramps. Returns the length of the output: aLength + bLength - overlap.
+        /// </summary>
+        /// <param name="a">The first audio samples.</param>
+        /// <param name="aLength">The length of the first audio data (can be less than the true length of the array).</param>
+        /// <param name="b">The second audio samples.</param>
+        /// <param name="bLength">The length of the second audio data (can be less than the true length of the array).</param>
+        /// <param name="overlap">The length of the crossfade in samples. This is clamped to be between 0 and the length of the shorter audio data.</param>
+        /// <param name="output">The output array. This must not be a or b. This will be resized if needed.</param>
+        public static int Crossfade(double[] a, int aLength, double[] b, int bLength, int overlap, ref double[] output)
+        {
+            overlap = Math.Max(Math.Min(overlap, Math.Min(aLength, bLength)), 0);
+            int outputLength = aLength + bLength - overlap;
+            if (output.Length < outputLength)
+            {
+                Array.Resize(ref output, outputLength * 2);
+            }
+            int start = aLength - overlap;
+            // Copy the start of a.
+            Buffer.BlockCopy(a, 0, output, 0, start * 8);
+            // Crossfade.
+            double angle;
+            for (int i = 0; i < overlap; i++)
+            {
+                angle = (double)i / overlap * Math.PI / 2;
+                output[start + i] = a[start + i] * Math.Cos(angle) + b[i] * Math.Sin(angle);
+            }
+            // Copy the end of b.
+            Buffer.BlockCopy(b, overlap * 8, output, aLength * 8, (bLength - overlap) * 8);
+            return outputLength;
+        }
+    }
+}
\ No newline at end of file
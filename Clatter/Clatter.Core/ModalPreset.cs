@@ -0,0 +1,85 @@
+﻿using System;
+
+
+namespace Clatter.Core
+{
+    /// <summary>
+    /// Derive plausible modes from a `PresetMaterial` and the size of an object. Use this if you don't have measured modal data for a material. The output can be passed to `Modes.Sum(frequencies, powers, decayTimes, resonance, synthSound)` or `Impact.Synthesize()`.
+    ///
+    /// The object is modeled as a free bar: the fundamental frequency is inversely proportional to the size, the overtones follow the inharmonic ratios of a free bar, and higher overtones are quieter and decay faster. The formulas aren't physically exact, but they are consistent, and the per-material coefficients can be tuned. Each coefficient array is indexed by the numerical value of the `PresetMaterial`.
+    /// </summary>
+    public static class ModalPreset
+    {
+        /// <summary>
+        /// The default number of modes.
+        /// </summary>
+        public const int DEFAULT_NUM_MODES = 10;
+        /// <summary>
+        /// The size of a reference object in meters.
+        /// </summary>
+        private const double REFERENCE_SIZE = 0.1;
+        /// <summary>
+        /// Modes at or above the Nyquist frequency are set to this power in dB, which silences them.
+        /// </summary>
+        private const double SILENT_POWER = -80;
+
+
+        /// <summary>
+        /// The fundamental frequency in Hz of a 0.1 meter object of each material.
+        /// </summary>
+        public static double[] fundamentals = new double[] { 800, 2500, 2000, 1600, 600 };
+        /// <summary>
+        /// The decay time in ms of the fundamental mode of a 0.1 meter object of each material.
+        /// </summary>
+        public static double[] decayTimes = new double[] { 80, 1500, 900, 500, 120 };
+        /// <summary>
+        /// The onset power in dB of the fundamental mode of each material.
+        /// </summary>
+        public static double[] powers = new double[] { -10, -15, -15, -12, -10 };
+        /// <summary>
+        /// The decrease in onset power per mode in dB for each material.
+        /// </summary>
+        public static double[] powerRolloffs = new double[] { 6, 2, 3, 4, 6 };
+        /// <summary>
+        /// For each material, higher modes decay faster by a factor of (fundamental / mode frequency)^damping. Materials with high internal damping (such as wood) have high values.
+        /// </summary>
+        public static double[] dampings = new double[] { 1, 0.3, 0.4, 0.6, 0.9 };
+
+
+        /// <summary>
+        /// Derive modes from a material and an object size.
+        /// </summary>
+        /// <param name="material">The material.</param>
+        /// <param name="size">The size of the object in meters. Larger objects have lower frequencies and longer decay times.</param>
+        /// <param name="frequencies">The mode frequencies in Hz.</param>
+        /// <param name="modePowers">The mode onset powers in dB.</param>
+        /// <param name="modeDecayTimes">The mode decay times in ms.</param>
+        /// <param name="numModes">The number of modes.</param>
+        public static void Get(PresetMaterial material, double size, out double[] frequencies, out double[] modePowers, out double[] modeDecayTimes, int numModes = DEFAULT_NUM_MODES)
+        {
+            if (size <= 0)
+            {
+                throw new Exception("Invalid size: " + size);
+            }
+            if (numModes < 1)
+            {
+                throw new Exception("Invalid number of modes: " + numModes);
+            }
+            int m = (int)material;
+            frequencies = new double[numModes];
+            modePowers = new double[numModes];
+            modeDecayTimes = new double[numModes];
+            double fundamental = fundamentals[m] * REFERENCE_SIZE / size;
+            double decayTime = decayTimes[m] * Math.Sqrt(size / REFERENCE_SIZE);
+            double nyquist = Globals.framerateD / 2;
+            for (int i = 0; i < numModes; i++)
+            {
+                // The ratio of a free bar's mode frequency to its fundamental frequency is approximately ((2n + 1) / 3.0112)^2 for n > 1.
+                double ratio = i == 0 ? 1 : Math.Pow((2 * (i + 1) + 1) / 3.0112, 2);
+                frequencies[i] = fundamental * ratio;
+                modeDecayTimes[i] = decayTime * Math.Pow(1 / ratio, dampings[m]);
+                modePowers[i] = frequencies[i] >= nyquist ? SILENT_POWER : powers[m] - powerRolloffs[m] * i;
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -12,7 +12,33 @@ namespace Clatter.Core
         /// The default length of the arrays.
         /// </summary>
         private const int MODES_DATA_LENGTH = 10;
+        /// <summary>
+        /// The frequency in Hz whose decay time isn't affected by dampingSlope.
+        /// </summary>
+        public const double DAMPING_REFERENCE_FREQUENCY = 1000;
+
 
+        /// <summary>
+        /// The standard deviation of the random jitter applied to each mode's frequency, as a fraction of the material's frequency. If this is 0, the material's frequencies are used as-is.
+        /// </summary>
+        public static double frequencyJitter = 0.1;
+        /// <summary>
+        /// The standard deviation of the random jitter applied to each mode's onset power in dB. If this is 0, the material's powers are used as-is.
+        /// </summary>
+        public static double powerJitter = 10;
+        /// <summary>
+        /// The standard deviation of the random jitter applied to each mode's decay time, as a fraction of the material's decay time. If this is 0, the material's decay times are used as-is.
+        /// </summary>
+        public static double decayTimeJitter = 0.1;
+        /// <summary>
+        /// The standard deviation of the random jitter in dB applied to each mode's power by AdjustPowers(), so that successive impacts aren't identical. If this is 0, successive impacts have the same powers.
+        /// </summary>
+        public static double powerAdjustmentJitter = 2;
+        /// <summary>
+        /// The frequency dependence of each mode's decay time. Real objects damp high-frequency modes faster than low-frequency modes. If this is greater than 0, each decay time is multiplied by (DAMPING_REFERENCE_FREQUENCY / frequency) ^ dampingSlope, meaning that modes above the reference frequency decay faster and modes below it decay slower. For example, a value of 1 halves the decay time of a mode whenever its frequency doubles. If this is 0, every mode is damped uniformly.
+        /// </summary>
+        public static double dampingSlope;
+        
         
         /// <summary>
         /// The cached synth sound array.
@@ -47,7 +73,7 @@ namespace Clatter.Core
 
 
         /// <summary>
-        /// Generate object modes data.
+        /// Generate object modes data. The material's values are randomly jittered; see: frequencyJitter, powerJitter, and decayTimeJitter. To generate reproducible modes, use a seeded random number generator.
         /// </summary>
         /// <param name="material">The impact material data.</param>
         /// <param name="rng">The random number generator.</param>
@@ -55,16 +81,24 @@ namespace Clatter.Core
         {
             for (int jm = 0; jm < MODES_DATA_LENGTH; jm++)
             {
-                double jf = 0;
-                while (jf < 20)
+                double jf = material.cf[jm];
+                if (frequencyJitter > 0)
                 {
-                    jf = material.cf[jm] + NormalDistribution.Random(0, material.cf[jm] / 10, rng);
+                    jf = 0;
+                    while (jf < 20)
+                    {
+                        jf = material.cf[jm] + NormalDistribution.Random(0, material.cf[jm] * frequencyJitter, rng);
+                    }
                 }
-                double jp = material.op[jm] + NormalDistribution.Random(0, 10, rng);
-                double jt = 0;
-                while (jt < 0.001f)
+                double jp = material.op[jm] + NormalDistribution.Random(0, powerJitter, rng);
+                double jt = material.rt[jm];
+                if (decayTimeJitter > 0)
                 {
-                    jt = material.rt[jm] + NormalDistribution.Random(0, material.rt[jm] / 10, rng);
+                    jt = 0;
+                    while (jt < 0.001f)
+                    {
+                        jt = material.rt[jm] + NormalDistribution.Random(0, material.rt[jm] * decayTimeJitter, rng);
+                    }
                 }
                 frequencies[jm] = jf;
                 powers[jm] = jp;
@@ -79,38 +113,90 @@ namespace Clatter.Core
         /// <param name="resonance">The object's audio resonance value. This affects the decay times of audio generated by this object.</param>
         public void Sum(double resonance)
         {
+            synthSoundLength = Sum(frequencies, powers, decayTimes, resonance, ref synthSound);
+        }
+
+
+        /// <summary>
+        /// Create a mode time-series from each mode's properties and sum them together. Returns the length of the summed data.
+        ///
+        /// In most cases, you'll want to call Sum(resonance) instead. Call this to synthesize modes that aren't derived from an `ImpactMaterialData`.
+        ///
+        /// Modes with a decay time less than or equal to 0 or a non-finite frequency, power, or decay time are skipped. If the resonance is less than or equal to 0, all of the modes are skipped and this returns 0.
+        /// </summary>
+        /// <param name="frequencies">Mode frequencies in Hz.</param>
+        /// <param name="powers">Mode onset powers in dB.</param>
+        /// <param name="decayTimes">Mode decay times i.e. the time in ms it takes for each mode to decay 60dB from its onset power.</param>
+        /// <param name="resonance">The object's audio resonance value. This affects the decay times of the modes.</param>
+        /// <param name="synthSound">The summed modes. This will be resized if needed.</param>
+        public static int Sum(double[] frequencies, double[] powers, double[] decayTimes, double resonance, ref double[] synthSound)
+        {
+            return Sum(frequencies, powers, decayTimes, null, resonance, ref synthSound);
+        }
+
+
+        /// <summary>
+        /// Create a mode time-series from each mode's properties and sum them together, starting each mode at a given phase. Returns the length of the summed data.
+        ///
+        /// By default, every mode starts at its peak (cosine phase), which can cause a large onset transient. Randomizing or staggering the phases spreads out the onset energy. If phases is null, or if every phase is 0, the output is the same as that of Sum(frequencies, powers, decayTimes, resonance, synthSound).
+        /// </summary>
+        /// <param name="frequencies">Mode frequencies in Hz.</param>
+        /// <param name="powers">Mode onset powers in dB.</param>
+        /// <param name="decayTimes">Mode decay times i.e. the time in ms it takes for each mode to decay 60dB from its onset power.</param>
+        /// <param name="phases">Mode start phases in radians. Can be null.</param>
+        /// <param name="resonance">The object's audio resonance value. This affects the decay times of the modes.</param>
+        /// <param name="synthSound">The summed modes. This will be resized if needed.</param>
+        public static int Sum(double[] frequencies, double[] powers, double[] decayTimes, double[] phases, double resonance, ref double[] synthSound)
+        {
+            return Sum(frequencies, powers, decayTimes, phases, null, resonance, ref synthSound);
+        }
+
+
+        /// <summary>
+        /// Create a mode time-series from each mode's properties and sum them together, starting each mode at a given phase and after a given delay. Returns the length of the summed data.
+        ///
+        /// When an object is struck off-center, its modes aren't all excited at the same time. Delaying the onset of each mode by a few samples creates a more diffuse, natural attack. If onsetDelays is null, or if every delay is 0, the output is the same as that of Sum(frequencies, powers, decayTimes, phases, resonance, synthSound).
+        /// </summary>
+        /// <param name="frequencies">Mode frequencies in Hz.</param>
+        /// <param name="powers">Mode onset powers in dB.</param>
+        /// <param name="decayTimes">Mode decay times i.e. the time in ms it takes for each mode to decay 60dB from its onset power.</param>
+        /// <param name="phases">Mode start phases in radians. Can be null.</param>
+        /// <param name="onsetDelays">Mode onset delays in samples. Each delay must be at least 0. Can be null.</param>
+        /// <param name="resonance">The object's audio resonance value. This affects the decay times of the modes.</param>
+        /// <param name="synthSound">The summed modes. This will be resized if needed.</param>
+        public static int Sum(double[] frequencies, double[] powers, double[] decayTimes, double[] phases, int[] onsetDelays, double resonance, ref double[] synthSound)
+        {
+            if (frequencies.Length != powers.Length || frequencies.Length != decayTimes.Length)
+            {
+                throw new Exception("Mode arrays have different lengths: " + frequencies.Length + ", " + powers.Length + ", " + decayTimes.Length);
+            }
+            if (phases != null && phases.Length != frequencies.Length)
+            {
+                throw new Exception("Mode arrays have different lengths: " + frequencies.Length + ", " + phases.Length);
+            }
+            if (onsetDelays != null && onsetDelays.Length != frequencies.Length)
+            {
+                throw new Exception("Mode arrays have different lengths: " + frequencies.Length + ", " + onsetDelays.Length);
+            }
             if (!setMode)
             {
                 setMode = true;
                 mode = new double[Globals.DEFAULT_SAMPLES_LENGTH];
             }
-            for (int i = 0; i < MODES_DATA_LENGTH; i++)
+            int synthSoundLength = 0;
+            for (int i = 0; i < frequencies.Length; i++)
             {
-                int modeCount = (int)Math.Ceiling((decayTimes[i] * (80.0 + powers[i]) / 60.0) / 1e3 * Globals.framerate);
-                // Clamp the count to positive values.
-                if (modeCount < 0)
-                {
-                    modeCount = 0;
-                }
-                // Resize the mode array.
-                if (mode.Length < modeCount)
+                int modeCount = GetMode(frequencies[i], powers[i], decayTimes[i], phases == null ? 0 : phases[i], resonance, ref mode);
+                int onsetDelay = onsetDelays == null ? 0 : onsetDelays[i];
+                if (onsetDelay < 0)
                 {
-                    Array.Resize(ref mode, modeCount);
+                    throw new Exception("Invalid mode onset delay: " + onsetDelay);
                 }
-                if (modeCount > 0)
+                if (onsetDelay > 0)
                 {
-                    // Synthesize a sinusoid.
-                    double pow = Math.Pow(10, powers[i] / 20);
-                    double dcy = -60 / (decayTimes[i] * resonance / 1e3) / 20;
-                    double q = 2 * frequencies[i] * Math.PI;
-                    double tt;
-                    for (int j = 0; j < modeCount; j++)
-                    {
-                        tt = j / Globals.framerateD;
-                        mode[j] = Math.Cos(tt * q) * pow * Math.Pow(10, tt * dcy);
-                    }
+                    synthSoundLength = AddDelayed(ref synthSound, synthSoundLength, mode, modeCount, onsetDelay);
                 }
-                if (i == 0)
+                else if (i == 0)
                 {
                     // Copy the first mode into the synth sound.
                     synthSoundLength = modeCount;
@@ -125,18 +211,207 @@ namespace Clatter.Core
                     synthSoundLength = Add(synthSound, synthSoundLength, mode, modeCount, ref synthSound);
                 }
             }
+            return synthSoundLength;
+        }
+
+
+        /// <summary>
+        /// Create a separate time-series for each mode without summing them together. This can be useful for identifying which mode is causing an artifact.
+        /// </summary>
+        /// <param name="frequencies">Mode frequencies in Hz.</param>
+        /// <param name="powers">Mode onset powers in dB.</param>
+        /// <param name="decayTimes">Mode decay times i.e. the time in ms it takes for each mode to decay 60dB from its onset power.</param>
+        /// <param name="resonance">The object's audio resonance value. This affects the decay times of the modes.</param>
+        /// <param name="modes">The time-series of each mode. This must have the same length as the other arrays. Each element will be resized if needed, or created if it's null.</param>
+        /// <param name="modeLengths">The length of each time-series. This must have the same length as the other arrays. A mode that is skipped (see: Sum()) has a length of 0.</param>
+        public static void GetModes(double[] frequencies, double[] powers, double[] decayTimes, double resonance, double[][] modes, int[] modeLengths)
+        {
+            if (frequencies.Length != powers.Length || frequencies.Length != decayTimes.Length)
+            {
+                throw new Exception("Mode arrays have different lengths: " + frequencies.Length + ", " + powers.Length + ", " + decayTimes.Length);
+            }
+            if (modes.Length != frequencies.Length || modeLengths.Length != frequencies.Length)
+            {
+                throw new Exception("Output arrays have a different length than the number of modes: " + frequencies.Length + ", " + modes.Length + ", " + modeLengths.Length);
+            }
+            for (int i = 0; i < frequencies.Length; i++)
+            {
+                if (modes[i] == null)
+                {
+                    modes[i] = new double[Globals.DEFAULT_SAMPLES_LENGTH];
+                }
+                modeLengths[i] = GetMode(frequencies[i], powers[i], decayTimes[i], 0, resonance, ref modes[i]);
+            }
+        }
+
+
+        /// <summary>
+        /// Returns the decay time in ms at which a mode decays by floorDb over tailLength samples. This is the inverse of the decay formula in Sum(): a mode decays 60 dB over decayTime * resonance ms.
+        /// </summary>
+        /// <param name="tailLength">The number of samples over which the mode should decay.</param>
+        /// <param name="resonance">The object's audio resonance value.</param>
+        /// <param name="floorDb">The level at the end of the tail in dB, relative to the mode's onset power. This must be negative, e.g. -60.</param>
+        public static double GetDecayTime(int tailLength, double resonance, double floorDb)
+        {
+            if (tailLength <= 0 || !(resonance > 0) || !(floorDb < 0))
+            {
+                throw new Exception("Invalid decay time parameters: " + tailLength + ", " + resonance + ", " + floorDb);
+            }
+            return -60 * (tailLength / Globals.framerateD) * 1e3 / (floorDb * resonance);
+        }
+
+
+        /// <summary>
+        /// Snap each frequency to the nearest note of a musical scale. This can be used for stylized or musical impact sounds. Call this before Sum().
+        ///
+        /// The scale repeats every octave, so for example a major scale is {0, 2, 4, 5, 7, 9, 11}. Frequencies that aren't positive and finite aren't modified.
+        /// </summary>
+        /// <param name="frequencies">The mode frequencies in Hz. This array will be modified.</param>
+        /// <param name="rootFrequency">The frequency of the root note of the scale in Hz.</param>
+        /// <param name="scale">The scale as semitone offsets from the root note. If this is null or empty, the frequencies aren't modified.</param>
+        public static void QuantizeToScale(double[] frequencies, double rootFrequency, double[] scale)
+        {
+            if (scale == null || scale.Length == 0)
+            {
+                return;
+            }
+            if (!(rootFrequency > 0) || double.IsInfinity(rootFrequency))
+            {
+                throw new Exception("Invalid root frequency: " + rootFrequency);
+            }
+            for (int i = 0; i < frequencies.Length; i++)
+            {
+                if (!(frequencies[i] > 0) || double.IsInfinity(frequencies[i]))
+                {
+                    continue;
+                }
+                // Convert the frequency to semitones relative to the root note.
+                double semitones = 12 * Math.Log(frequencies[i] / rootFrequency, 2);
+                double octave = Math.Floor(semitones / 12) * 12;
+                double offset = semitones - octave;
+                // Find the nearest note, including the notes in the adjacent octaves.
+                double nearest = 0;
+                double minDistance = double.MaxValue;
+                for (int j = 0; j < scale.Length; j++)
+                {
+                    double note = scale[j] - Math.Floor(scale[j] / 12) * 12;
+                    for (int k = -12; k <= 12; k += 12)
+                    {
+                        double distance = Math.Abs(offset - (note + k));
+                        if (distance < minDistance)
+                        {
+                            minDistance = distance;
+                            nearest = note + k;
+                        }
+                    }
+                }
+                frequencies[i] = rootFrequency * Math.Pow(2, (octave + nearest) / 12);
+            }
         }
 
 
         /// <summary>
-        /// Adjust the powers.
+        /// Linearly interpolate between two sets of modes, for example to create a composite material at runtime from two impact materials. Each mode in the first set is interpolated with the corresponding mode in the second set. The output can be passed to Sum().
+        /// </summary>
+        /// <param name="aFrequencies">The first set's mode frequencies in Hz.</param>
+        /// <param name="aPowers">The first set's mode onset powers in dB.</param>
+        /// <param name="aDecayTimes">The first set's mode decay times in ms.</param>
+        /// <param name="bFrequencies">The second set's mode frequencies in Hz.</param>
+        /// <param name="bPowers">The second set's mode onset powers in dB.</param>
+        /// <param name="bDecayTimes">The second set's mode decay times in ms.</param>
+        /// <param name="t">The morph factor (0 to 1). If this is 0, the output is the first set. If this is 1, the output is the second set.</param>
+        /// <param name="frequencies">The interpolated mode frequencies in Hz. This will be resized if needed.</param>
+        /// <param name="powers">The interpolated mode onset powers in dB. This will be resized if needed.</param>
+        /// <param name="decayTimes">The interpolated mode decay times in ms. This will be resized if needed.</param>
+        public static void Morph(double[] aFrequencies, double[] aPowers, double[] aDecayTimes, double[] bFrequencies, double[] bPowers, double[] bDecayTimes, double t, ref double[] frequencies, ref double[] powers, ref double[] decayTimes)
+        {
+            int length = aFrequencies.Length;
+            if (aPowers.Length != length || aDecayTimes.Length != length || bFrequencies.Length != length || bPowers.Length != length || bDecayTimes.Length != length)
+            {
+                throw new Exception("Mode arrays have different lengths: " + length + ", " + aPowers.Length + ", " + aDecayTimes.Length + ", " + bFrequencies.Length + ", " + bPowers.Length + ", " + bDecayTimes.Length);
+            }
+            if (!(t >= 0 && t <= 1))
+            {
+                throw new Exception("Invalid morph factor: " + t);
+            }
+            if (frequencies.Length != length)
+            {
+                Array.Resize(ref frequencies, length);
+            }
+            if (powers.Length != length)
+            {
+                Array.Resize(ref powers, length);
+            }
+            if (decayTimes.Length != length)
+            {
+                Array.Resize(ref decayTimes, length);
+            }
+            for (int i = 0; i < length; i++)
+            {
+                frequencies[i] = aFrequencies[i] + (bFrequencies[i] - aFrequencies[i]) * t;
+                powers[i] = aPowers[i] + (bPowers[i] - aPowers[i]) * t;
+                decayTimes[i] = aDecayTimes[i] + (bDecayTimes[i] - aDecayTimes[i]) * t;
+            }
+        }
+
+
+        /// <summary>
+        /// Create a time-series of a single mode. Returns the length of the time-series, which is 0 if the mode is degenerate.
+        /// </summary>
+        /// <param name="frequency">The mode frequency in Hz.</param>
+        /// <param name="power">The mode onset power in dB.</param>
+        /// <param name="decayTime">The mode decay time in ms.</param>
+        /// <param name="phase">The mode start phase in radians.</param>
+        /// <param name="resonance">The object's audio resonance value.</param>
+        /// <param name="mode">The time-series. This will be resized if needed.</param>
+        private static int GetMode(double frequency, double power, double decayTime, double phase, double resonance, ref double[] mode)
+        {
+            // Skip degenerate modes, which would otherwise fill the output with NaN or infinity.
+            if (!(decayTime > 0) || !(resonance > 0) || !frequency.IsFinite() || !power.IsFinite() || double.IsInfinity(decayTime))
+            {
+                return 0;
+            }
+            // Scale the decay time by the frequency. See: dampingSlope.
+            if (dampingSlope != 0 && frequency > 0)
+            {
+                decayTime *= Math.Pow(DAMPING_REFERENCE_FREQUENCY / frequency, dampingSlope);
+            }
+            int modeCount = (int)Math.Ceiling((decayTime * (80.0 + power) / 60.0) / 1e3 * Globals.framerate);
+            // Clamp the count to positive values.
+            if (modeCount <= 0)
+            {
+                return 0;
+            }
+            // Resize the mode array.
+            if (mode.Length < modeCount)
+            {
+                Array.Resize(ref mode, modeCount);
+            }
+            // Synthesize a sinusoid.
+            double pow = Math.Pow(10, power / 20);
+            double dcy = -60 / (decayTime * resonance / 1e3) / 20;
+            double q = 2 * frequency * Math.PI;
+            // The decay is exponential, so 10^(t * dcy) can be calculated by multiplying the envelope by a constant every sample.
+            double decayStep = Math.Pow(10, dcy / Globals.framerateD);
+            double envelope = pow;
+            for (int j = 0; j < modeCount; j++)
+            {
+                mode[j] = Math.Cos(j / Globals.framerateD * q + phase) * envelope;
+                envelope *= decayStep;
+            }
+            return modeCount;
+        }
+
+
+        /// <summary>
+        /// Randomly adjust the powers. See: powerAdjustmentJitter.
         /// </summary>
         /// <param name="rng">The random number generator.</param>
         public void AdjustPowers(Random rng)
         {
             for (int i = 0; i < MODES_DATA_LENGTH; i++)
             {
-                powers[i] += NormalDistribution.Random(0, 2, rng);
+                powers[i] += NormalDistribution.Random(0, powerAdjustmentJitter, rng);
             }
         }
 
@@ -155,7 +430,7 @@ namespace Clatter.Core
 
 
         /// <summary>
-        /// Add together arrays of different lengths by zero-padding the shorter.
+        /// Add together arrays of different lengths by zero-padding the shorter. The output array can be the same as one of the input arrays.
         /// </summary>
         /// <param name="a">The first array.</param>
         /// <param name="aLength">The length of the first array (can be less than the true length).</param>
@@ -165,31 +440,59 @@ namespace Clatter.Core
         public static int Add(double[] a, int aLength, double[] b, int bLength, ref double[] added)
         {
             int length;
+            int shorterLength;
+            double[] longer;
             if (aLength < bLength)
             {
                 length = bLength;
-                if (added.Length < length)
-                {
-                    Array.Resize(ref added, length);
-                }
-                Buffer.BlockCopy(b, 0, added, 0, length * 8);
-                for (int i = 0; i < aLength; i++)
-                {
-                    added[i] += a[i];
-                }
+                shorterLength = aLength;
+                longer = b;
             }
             else
             {
                 length = aLength;
-                if (added.Length < length)
-                {
-                    Array.Resize(ref added, length);
-                }
-                Buffer.BlockCopy(a, 0, added, 0, length * 8);
-                for (int i = 0; i < bLength; i++)
-                {
-                    added[i] += b[i];
-                }
+                shorterLength = bLength;
+                longer = a;
+            }
+            if (added.Length < length)
+            {
+                Array.Resize(ref added, length);
+            }
+            // Add the overlapping values. Each value is read before it is written, so the output can be one of the inputs.
+            for (int i = 0; i < shorterLength; i++)
+            {
+                added[i] = a[i] + b[i];
+            }
+            // Copy the rest of the longer array.
+            Buffer.BlockCopy(longer, shorterLength * 8, added, shorterLength * 8, (length - shorterLength) * 8);
+            return length;
+        }
+
+
+        /// <summary>
+        /// Add a delayed mode to the synth sound, zero-padding the synth sound if the delayed mode is longer. Returns the length of the synth sound.
+        /// </summary>
+        /// <param name="synthSound">The synth sound. This will be resized if needed.</param>
+        /// <param name="synthSoundLength">The length of the synth sound.</param>
+        /// <param name="mode">The mode time-series.</param>
+        /// <param name="modeCount">The length of the mode time-series.</param>
+        /// <param name="onsetDelay">The onset delay in samples.</param>
+        private static int AddDelayed(ref double[] synthSound, int synthSoundLength, double[] mode, int modeCount, int onsetDelay)
+        {
+            if (modeCount == 0)
+            {
+                return synthSoundLength;
+            }
+            int length = Math.Max(synthSoundLength, onsetDelay + modeCount);
+            if (synthSound.Length < length)
+            {
+                Array.Resize(ref synthSound, length);
+            }
+            // Clear the padding.
+            Array.Clear(synthSound, synthSoundLength, length - synthSoundLength);
+            for (int i = 0; i < modeCount; i++)
+            {
+                synthSound[onsetDelay + i] += mode[i];
             }
             return length;
         }
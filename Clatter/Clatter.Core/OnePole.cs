@@ -0,0 +1,78 @@
+﻿using System;
+
+
+namespace Clatter.Core
+{
+    /// <summary>
+    /// A one-pole low-pass filter that smooths changes to a parameter, for example a scrape speed. Parameters that are updated by a physics engine often change less frequently than audio is generated, which can cause audible steps. A OnePole glides towards each new value instead.
+    /// </summary>
+    public class OnePole
+    {
+        /// <summary>
+        /// The per-sample smoothing coefficient.
+        /// </summary>
+        private readonly double coefficient;
+        /// <summary>
+        /// The current value.
+        /// </summary>
+        private double value;
+
+
+        /// <summary>
+        /// (constructor)
+        /// </summary>
+        /// <param name="timeConstant">The time constant in milliseconds i.e. the time it takes to move approximately 63% of the way towards a new value. If this is 0, new values are applied immediately.</param>
+        /// <param name="value">The initial value.</param>
+        public OnePole(double timeConstant, double value = 0)
+        {
+            if (timeConstant < 0)
+            {
+                throw new Exception("Invalid time constant: " + timeConstant);
+            }
+            coefficient = timeConstant > 0 ? Math.Exp(-1 / (timeConstant / 1e3 * Globals.framerateD)) : 0;
+            this.value = value;
+        }
+
+
+        /// <summary>
+        /// Move towards a target value by one sample. Returns the smoothed value.
+        /// </summary>
+        /// <param name="target">The target value.</param>
+        public double ProcessSample(double target)
+        {
+            value = target + coefficient * (value - target);
+            return value;
+        }
+
+
+        /// <summary>
+        /// Move towards a target value by a number of samples, for example the length of a block of audio. Returns the smoothed value. This is equivalent to, but faster than, calling ProcessSample() length times.
+        /// </summary>
+        /// <param name="target">The target value.</param>
+        /// <param name="length">The number of samples.</param>
+        public double Process(double target, int length)
+        {
+            value = target + Math.Pow(coefficient, length) * (value - target);
+            return value;
+        }
+
+
+        /// <summary>
+        /// Returns the current smoothed value.
+        /// </summary>
+        public double GetValue()
+        {
+            return value;
+        }
+
+
+        /// <summary>
+        /// Set the smoothed value without smoothing.
+        /// </summary>
+        /// <param name="value">The new value.</param>
+        public void Reset(double value = 0)
+        {
+            this.value = value;
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,14 @@
+﻿namespace Clatter.Core
+{
+    /// <summary>
+    /// Broad material categories used to derive modes from physical properties. See: `ModalPreset`.
+    /// </summary>
+    public enum PresetMaterial : byte
+    {
+        wood = 0,
+        metal = 1,
+        glass = 2,
+        ceramic = 3,
+        plastic = 4
+    }
+}
\ No newline at end of file
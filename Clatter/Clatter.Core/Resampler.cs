@@ -0,0 +1,161 @@
+﻿using System;
+
+
+namespace Clatter.Core
+{
+    /// <summary>
+    /// Resample audio from one framerate to another, for example if your audio engine's framerate isn't the same as `Globals.framerate`.
+    ///
+    /// The output length is inputLength * outRate / inRate, rounded to the nearest integer.
+    /// </summary>
+    public static class Resampler
+    {
+        /// <summary>
+        /// The default half-width of the sinc window, in zero crossings.
+        /// </summary>
+        public const int DEFAULT_SINC_WINDOW = 16;
+
+
+        /// <summary>
+        /// Resample audio using linear interpolation. This is fast but can add some aliasing and high-frequency loss. Returns the length of the output.
+        /// </summary>
+        /// <param name="input">The input audio samples.</param>
+        /// <param name="inputLength">The length of the input audio data (can be less than the true length of the array).</param>
+        /// <param name="inRate">The framerate of the input audio.</param>
+        /// <param name="outRate">The framerate of the output audio.</param>
+        /// <param name="output">The output array. This will be resized if needed.</param>
+        public static int Linear(double[] input, int inputLength, double inRate, double outRate, ref double[] output)
+        {
+            int outputLength = GetOutputLength(inputLength, inRate, outRate);
+            if (outputLength == 0)
+            {
+                return 0;
+            }
+            if (output.Length < outputLength)
+            {
+                Array.Resize(ref output, outputLength * 2);
+            }
+            double step = inRate / outRate;
+            double x;
+            int index;
+            for (int i = 0; i < outputLength; i++)
+            {
+                x = i * step;
+                index = (int)x;
+                if (index >= inputLength - 1)
+                {
+                    output[i] = input[inputLength - 1];
+                }
+                else
+                {
+                    output[i] = input[index] + (input[index + 1] - input[index]) * (x - index);
+                }
+            }
+            return outputLength;
+        }
+
+
+        /// <summary>
+        /// Resample audio using Hann-windowed sinc interpolation. This is slower than Linear() but has much less aliasing. When downsampling, the sinc kernel is widened to act as an anti-aliasing low-pass filter. Returns the length of the output.
+        /// </summary>
+        /// <param name="input">The input audio samples.</param>
+        /// <param name="inputLength">The length of the input audio data (can be less than the true length of the array).</param>
+        /// <param name="inRate">The framerate of the input audio.</param>
+        /// <param name="outRate">The framerate of the output audio.</param>
+        /// <param name="output">The output array. This will be resized if needed.</param>
+        /// <param name="window">The half-width of the sinc window, in zero crossings. A larger window is more accurate but slower.</param>
+        public static int Sinc(double[] input, int inputLength, double inRate, double outRate, ref double[] output, int window = DEFAULT_SINC_WINDOW)
+        {
+            if (window < 1)
+            {
+                throw new Exception("Invalid sinc window: " + window);
+            }
+            int outputLength = GetOutputLength(inputLength, inRate, outRate);
+            if (outputLength == 0)
+            {
+                return 0;
+            }
+            if (output.Length < outputLength)
+            {
+                Array.Resize(ref output, outputLength * 2);
+            }
+            double step = inRate / outRate;
+            // The cutoff frequency as a fraction of the input Nyquist frequency.
+            double cutoff = Math.Min(1, outRate / inRate);
+            // The radius of the kernel in input samples.
+            double radius = window / cutoff;
+            double x;
+            double t;
+            double sum;
+            int start;
+            int end;
+            for (int i = 0; i < outputLength; i++)
+            {
+                x = i * step;
+                start = Math.Max((int)Math.Ceiling(x - radius), 0);
+                end = Math.Min((int)Math.Floor(x + radius), inputLength - 1);
+                sum = 0;
+                for (int j = start; j <= end; j++)
+                {
+                    t = x - j;
+                    sum += input[j] * cutoff * Normalized(cutoff * t) * (0.5 + 0.5 * Math.Cos(Math.PI * t / radius));
+                }
+                output[i] = sum;
+            }
+            return outputLength;
+        }
+
+
+        /// <summary>
+        /// Shift the pitch of audio by resampling it with Sinc(). This can be used to create variations of one impact at different pitches without synthesizing it again. Returns the length of the output.
+        ///
+        /// This is a naive pitch shift: the duration changes with the pitch. Shifting the pitch up by 12 semitones (an octave) halves the duration, and shifting it down by 12 semitones doubles the duration.
+        /// </summary>
+        /// <param name="input">The input audio samples.</param>
+        /// <param name="inputLength">The length of the input audio data (can be less than the true length of the array).</param>
+        /// <param name="semitones">The pitch shift in semitones. Positive values raise the pitch.</param>
+        /// <param name="output">The output array. This must not be the input array. This will be resized if needed.</param>
+        /// <param name="window">The half-width of the sinc window, in zero crossings. A larger window is more accurate but slower.</param>
+        public static int PitchShift(double[] input, int inputLength, double semitones, ref double[] output, int window = DEFAULT_SINC_WINDOW)
+        {
+            // Resampling to a lower framerate and then playing the audio at the original framerate raises the pitch.
+            double ratio = Math.Pow(2, semitones / 12);
+            return Sinc(input, inputLength, Globals.framerateD, Globals.framerateD / ratio, ref output, window);
+        }
+
+
+        /// <summary>
+        /// Returns the length of resampled audio.
+        /// </summary>
+        /// <param name="inputLength">The length of the input audio data (can be less than the true length of the array).</param>
+        /// <param name="inRate">The framerate of the input audio.</param>
+        /// <param name="outRate">The framerate of the output audio.</param>
+        public static int GetOutputLength(int inputLength, double inRate, double outRate)
+        {
+            if (inRate <= 0 || outRate <= 0)
+            {
+                throw new Exception("Invalid framerates: " + inRate + ", " + outRate);
+            }
+            if (inputLength <= 0)
+            {
+                return 0;
+            }
+            return (int)Math.Round(inputLength * outRate / inRate);
+        }
+
+
+        /// <summary>
+        /// Returns the normalized sinc function: sin(pi * x) / (pi * x).
+        /// </summary>
+        /// <param name="x">The input value.</param>
+        private static double Normalized(double x)
+        {
+            if (x == 0)
+            {
+                return 1;
+            }
+            double px = Math.PI * x;
+            return Math.Sin(px) / px;
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,67 @@
+﻿using System;
+
+
+namespace Clatter.Core
+{
+    /// <summary>
+    /// Generate parametric reverb impulse responses. An impulse response can be convolved with audio (see: DoubleExtensions.Convolve()) to add a room tail without needing to record or source an impulse response file.
+    /// </summary>
+    public static class Reverb
+    {
+        /// <summary>
+        /// Generate a reverb impulse response: a pre-delay of silence followed by exponentially decaying Gaussian noise. The noise decays by 60 dB over rt60 seconds. Returns the length of the impulse response. See: GetImpulseResponseLength().
+        /// </summary>
+        /// <param name="rt60">The reverberation time in seconds i.e. the time it takes for the tail to decay by 60 dB.</param>
+        /// <param name="preDelay">The pre-delay in milliseconds.</param>
+        /// <param name="rng">The random number generator. To generate a reproducible impulse response, use a seeded random number generator.</param>
+        /// <param name="impulseResponse">The impulse response. To avoid reallocating, make this at least GetImpulseResponseLength(rt60, preDelay) long. This will be resized if needed.</param>
+        public static int GetImpulseResponse(double rt60, double preDelay, Random rng, ref double[] impulseResponse)
+        {
+            int length = GetImpulseResponseLength(rt60, preDelay);
+            if (impulseResponse.Length < length)
+            {
+                Array.Resize(ref impulseResponse, length);
+            }
+            int preDelayLength = GetPreDelayLength(preDelay);
+            Array.Clear(impulseResponse, 0, preDelayLength);
+            // The decay is exponential, so the envelope can be calculated by multiplying it by a constant every sample.
+            double decayStep = Math.Pow(10, -3 / (rt60 * Globals.framerateD));
+            double envelope = 1;
+            for (int i = preDelayLength; i < length; i++)
+            {
+                impulseResponse[i] = NormalDistribution.Random(0, 1, rng) * envelope;
+                envelope *= decayStep;
+            }
+            return length;
+        }
+
+
+        /// <summary>
+        /// Returns the length of a reverb impulse response in samples: the pre-delay plus rt60 seconds, i.e. round(preDelay / 1000 * Globals.framerate) + ceil(rt60 * Globals.framerate).
+        /// </summary>
+        /// <param name="rt60">The reverberation time in seconds. This must be greater than 0.</param>
+        /// <param name="preDelay">The pre-delay in milliseconds. This must be at least 0.</param>
+        public static int GetImpulseResponseLength(double rt60, double preDelay)
+        {
+            if (!(rt60 > 0) || double.IsInfinity(rt60))
+            {
+                throw new Exception("Invalid reverberation time: " + rt60);
+            }
+            return GetPreDelayLength(preDelay) + (int)Math.Ceiling(rt60 * Globals.framerateD);
+        }
+
+
+        /// <summary>
+        /// Returns the length of the pre-delay in samples.
+        /// </summary>
+        /// <param name="preDelay">The pre-delay in milliseconds.</param>
+        private static int GetPreDelayLength(double preDelay)
+        {
+            if (!(preDelay >= 0) || double.IsInfinity(preDelay))
+            {
+                throw new Exception("Invalid pre-delay: " + preDelay);
+            }
+            return (int)Math.Round(preDelay / 1e3 * Globals.framerateD);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,120 @@
+﻿using System;
+
+
+namespace Clatter.Core
+{
+    /// <summary>
+    /// Generates roll audio, for example a ball rolling on a surface.
+    ///
+    /// A Roll is a series of continuous events, like a `Scrape`. By repeatedly calling GetAudio(), the roll event will continue. A Roll uses the same surface data and the same scrape options (for example, Scrape.interpolationMode and Scrape.removeDcOffset) as a `Scrape`, but the force is modulated by the rotation of the rolling object, which creates a quasi-periodic texture. The rotation frequency is derived from the speed and the radius of the rolling object, assuming that it rolls without slipping.
+    ///
+    /// Roll events are not yet automatically generated by `AudioGenerator`. You can manually create a Roll and use it to generate audio.
+    /// </summary>
+    public class Roll : SurfaceAudioEvent
+    {
+        /// <summary>
+        /// The default length of the roll samples. See: Scrape.samplesLength.
+        /// </summary>
+        public const int SAMPLES_LENGTH = Scrape.SAMPLES_LENGTH;
+
+
+        /// <summary>
+        /// When setting the amplitude for a roll, multiply `AudioEvent.simulationAmp` by this factor.
+        /// </summary>
+        public static double rollAmp = 1;
+        /// <summary>
+        /// For the purposes of roll audio generation, the speed is clamped to this maximum value in meters per second.
+        /// </summary>
+        public static double maxSpeed = 5;
+        /// <summary>
+        /// The depth of the periodic force modulation (0 to 1). At 0, the force isn't modulated by the rotation of the rolling object.
+        /// </summary>
+        public static double modulationDepth = 0.5;
+        /// <summary>
+        /// The ID of this roll event. This is used to track an ongoing roll.
+        /// </summary>
+        public readonly int rollId;
+        /// <summary>
+        /// The radius of the rolling object in meters.
+        /// </summary>
+        private readonly double radius;
+        /// <summary>
+        /// The rotation phase of the rolling object in radians. This is carried over between calls to GetAudio() so that the modulation is continuous.
+        /// </summary>
+        private double phase;
+        /// <summary>
+        /// A cached buffer for the per-sample force modulation.
+        /// </summary>
+        private readonly double[] modulation;
+
+
+        /// <summary>
+        /// (constructor)
+        /// </summary>
+        /// <param name="scrapeMaterial">The scrape material of the surface.</param>
+        /// <param name="primary">The primary object (the rolling object).</param>
+        /// <param name="secondary">The secondary object (the surface).</param>
+        /// <param name="radius">The radius of the rolling object in meters.</param>
+        /// <param name="rng">The random number generator.</param>
+        public Roll(ScrapeMaterial scrapeMaterial, ClatterObjectData primary, ClatterObjectData secondary, double radius, Random rng) : this(ScrapeMaterialData.Get(scrapeMaterial), primary, secondary, radius, rng)
+        {
+        }
+
+
+        /// <summary>
+        /// (constructor)
+        /// </summary>
+        /// <param name="scrapeMaterialData">The surface data. See: `Scrape`.</param>
+        /// <param name="primary">The primary object (the rolling object).</param>
+        /// <param name="secondary">The secondary object (the surface).</param>
+        /// <param name="radius">The radius of the rolling object in meters.</param>
+        /// <param name="rng">The random number generator.</param>
+        public Roll(ScrapeMaterialData scrapeMaterialData, ClatterObjectData primary, ClatterObjectData secondary, double radius, Random rng) : base(scrapeMaterialData, primary, secondary, rng)
+        {
+            if (radius <= 0)
+            {
+                throw new Exception("Invalid roll radius: " + radius);
+            }
+            this.radius = radius;
+            rollId = rng.Next();
+            modulation = new double[length];
+        }
+
+
+        /// <summary>
+        /// Generate audio. Returns true if audio was generated. This will set the `samples` field.
+        /// </summary>
+        /// <param name="speed">The linear speed of the rolling object in meters per second. The angular speed is speed / radius.</param>
+        public override bool GetAudio(double speed)
+        {
+            double rollSpeed = Math.Min(speed, maxSpeed);
+            // Get the per-sample change in the rotation phase: angular speed = speed / radius.
+            double deltaPhase = (rollSpeed / radius) / Globals.framerateD;
+            // Modulate the force by the rotation of the rolling object.
+            double p = phase;
+            for (int i = 0; i < length; i++)
+            {
+                modulation[i] = 1 + modulationDepth * Math.Sin(p);
+                p += deltaPhase;
+            }
+            // Calculate the force the same way as a scrape.
+            if (!GetAudio(speed, maxSpeed, rollAmp, modulation))
+            {
+                return false;
+            }
+            // Keep the phase small to avoid losing precision.
+            phase = p % (2 * Math.PI);
+            return true;
+        }
+
+
+        /// <summary>
+        /// Reset the continuation state of this roll (the position on the surface and the filters) so that the next call to GetAudio() starts a new, independent roll. The impulse response is kept.
+        /// </summary>
+        public override void Reset()
+        {
+            base.Reset();
+            phase = 0;
+        }
+    }
+}
\ No newline at end of file
@@ -1,4 +1,7 @@
-﻿namespace Clatter.Core
+﻿using System;
+
+
+namespace Clatter.Core
 {
     /// <summary>
     /// Cached audio data as an array of doubles that can be converted to an array of floats (for usage in Unity) or a byte array of int16 data (for .wav files).
@@ -32,18 +35,58 @@
         /// <summary>
         /// Returns the samples as floats. Use this in Unity, which accepts an array of floats for AudioClip data.
         /// </summary>
-        public float[] ToFloats()
+        /// <param name="clamp">If true, clamp each value to be between -1 and 1 and convert NaN and infinity to 0.</param>
+        public float[] ToFloats(bool clamp = false)
         {
-            return samples.ToFloats(length);
+            return samples.ToFloats(length, clamp);
+        }
+
+
+        /// <summary>
+        /// Returns the samples as 16-bit PCM values. Each value is clamped to be between -1 and 1, and NaN and infinity are converted to 0.
+        /// </summary>
+        /// <param name="dither">The random number generator used for dither. To generate reproducible dither, use a seeded random number generator. If this is null, there is no dither.</param>
+        public short[] ToInt16s(Random dither = null)
+        {
+            return samples.ToInt16s(length, dither);
         }
 
 
         /// <summary>
         /// Returns the samples as an int16 byte array. Use this to write out valid .wav file data.
         /// </summary>
-        public byte[] ToInt16Bytes()
+        /// <param name="dither">The random number generator used for dither. To generate reproducible dither, use a seeded random number generator. If this is null, there is no dither.</param>
+        public byte[] ToInt16Bytes(Random dither = null)
+        {
+            return samples.ToInt16Bytes(length, dither);
+        }
+
+
+        /// <summary>
+        /// Returns the samples as a float32 byte array. Use this to write out valid float .wav file data.
+        /// </summary>
+        public byte[] ToFloatBytes()
+        {
+            return samples.ToFloatBytes(length);
+        }
+
+
+        /// <summary>
+        /// Replace each NaN or infinity in the samples with 0. Returns the number of samples that were replaced.
+        /// </summary>
+        public int Sanitize()
+        {
+            return samples.Sanitize(length);
+        }
+
+
+        /// <summary>
+        /// Remove single-sample clicks from the samples. Returns the number of clicks that were removed. See: DoubleExtensions.Declick().
+        /// </summary>
+        /// <param name="threshold">The threshold, e.g. 0.5.</param>
+        public int Declick(double threshold)
         {
-            return samples.ToInt16Bytes(length);
+            return samples.Declick(length, threshold);
         }
     }
 }
\ No newline at end of file
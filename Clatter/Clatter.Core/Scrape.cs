@@ -1,4 +1,7 @@
 ﻿using System;
+using System.Diagnostics;
+using System.Runtime.ExceptionServices;
+using System.Threading;
 
 
 namespace Clatter.Core
@@ -15,16 +18,12 @@ namespace Clatter.Core
     /// {code_example:ScrapeAudioExample}
     ///
     /// </summary>
-    public class Scrape : AudioEvent
+    public class Scrape : SurfaceAudioEvent
     {
         /// <summary>
-        /// The length of the scrape samples.
+        /// The default length of the scrape samples. See: samplesLength.
         /// </summary>
         public const int SAMPLES_LENGTH = 4410;
-        /// <summary>
-        /// The default impulse response length.
-        /// </summary>
-        private const int DEFAULT_IMPULSE_RESPONSE_LENGTH = 9000;
         
         
         /// <summary>
@@ -36,41 +35,57 @@ namespace Clatter.Core
         /// </summary>
         public static double maxSpeed = 5;
         /// <summary>
-        /// The ID of this scrape event. This is used to track an ongoing scrape.
+        /// The window size of each scrape's median filter, which smooths the vertical force. A larger window (e.g. 7 or 9) will suppress more spikes, which can be useful for rough surfaces. A smaller window (e.g. 3) will preserve more transients, which can be useful for smooth surfaces. This is applied to new scrapes only.
         /// </summary>
-        public readonly int scrapeId;
+        public static int medianFilterWindowSize = MedianFilter.DEFAULT_WINDOW_SIZE;
+        /// <summary>
+        /// If true, don't apply the median filter to the vertical force. This is faster, and for very smooth surfaces the filter is unnecessary because there are no spikes to suppress. If the vertical force is monotonic within each filter window, the median is the middle sample of the window, so the only difference between the filtered and unfiltered force is a delay of (medianFilterWindowSize - 1) / 2 samples.
+        /// </summary>
+        public static bool bypassMedianFilter;
+        /// <summary>
+        /// If true, each scrape's median filter is adaptive: its window narrows when the vertical force changes suddenly and then widens back to medianFilterWindowSize. This preserves transients on surfaces that alternate between smooth and rough regions. This is applied to new scrapes only. See: MedianFilter.
+        /// </summary>
+        public static bool adaptiveMedianFilter;
+        /// <summary>
+        /// The length of the samples generated per call to GetAudio(). Shorter lengths have lower latency, because each block of audio is generated sooner, but GetAudio() must be called more often and the surface is sampled at a coarser resolution. Longer lengths have higher latency but are better for offline audio generation. This is applied to new scrapes and rolls only.
+        /// </summary>
+        public static int samplesLength = SAMPLES_LENGTH;
+        /// <summary>
+        /// The sharpness of the saturation curve applied to the vertical force: tanh(saturation * x) / tanh(saturation) * tanh(1). A value of 1 is the default curve. Higher values saturate sooner, which can sound like a harder contact. Lower values are gentler, which can sound like a softer contact. If this is less than or equal to 0, the curve is linear.
+        /// </summary>
+        public static double saturation = 1;
         /// <summary>
-        /// The previous index in the scrape surface array.
+        /// The oversampling factor of the saturation curve, for example 2 or 4. The saturation curve is a nonlinearity that adds high frequencies, some of which are folded back into the audible range (aliasing). If this is greater than 1, the vertical force is calculated at this many evenly spaced points per sample and then averaged, which reduces aliasing but is slower. The average is a box filter, which only approximates band-limited downsampling: it attenuates the aliased frequencies rather than removing them. If this is less than or equal to 1, the vertical force isn't oversampled.
         /// </summary>
-        private int scrapeIndex;
+        public static int saturationOversampling = 1;
         /// <summary>
-        /// A cached buffer for the force.
+        /// The function used to interpolate the scrape surface. Linear interpolation is faster, but the slope of the interpolated force changes abruptly at each surface point, which can add high-frequency grit. Cubic interpolation is smoother.
         /// </summary>
-        private readonly double[] force = new double[SAMPLES_LENGTH];
+        public static InterpolationMode interpolationMode = InterpolationMode.linear;
         /// <summary>
-        /// The scrape material data for this scrape.
+        /// If true, remove DC offset from scrape audio. The scrape force has a non-zero mean, which after convolution can cause a slowly drifting DC offset, audible thumps, and wasted headroom.
         /// </summary>
-        private readonly ScrapeMaterialData scrapeMaterialData;
+        public static bool removeDcOffset;
         /// <summary>
-        /// The cached impulse response array.
+        /// If true, convolve scrape and roll audio with the overlap-add method (see: `StreamingConvolver`): the convolution tail of each block is retained and added to the next block. This removes the clicks at block boundaries of sustained scrapes that are caused by discarding the tail of each block, but it is slower. The first block of a scrape doesn't have a previous tail, so this can be changed between scrapes.
         /// </summary>
-        private double[] impulseResponse = new double[DEFAULT_IMPULSE_RESPONSE_LENGTH];
+        public static bool overlapAdd;
         /// <summary>
-        /// If true, we've generated the impulse response.
+        /// If true, treat the scrape surface as a circular buffer: when a scrape reaches the end of the surface, it continues smoothly from the start. This is useful for looping a short surface profile whose end matches its start. If false, the scrape jumps back to the start of the surface, which can cause an audible discontinuity each time the surface repeats.
         /// </summary>
-        private bool gotImpulseResponse;
+        public static bool circularSurface;
         /// <summary>
-        /// The cached linear space array. The length of this can change depending on the speed of the scrape.
+        /// If true, apply a smoothstep curve to the ratio of the scrape speed to maxSpeed before it is used to scale the horizontal and vertical forces. This makes scrapes fade in and out more gently at low speeds.
         /// </summary>
-        private double[] linearSpace = new double[DEFAULT_IMPULSE_RESPONSE_LENGTH];
+        public static bool smoothSpeed;
         /// <summary>
-        /// A cached median filter used for smoothing over the sound.
+        /// If true, divide scrape audio by the L2 norm of the impulse response (see: DoubleExtensions.GetL2Norm()). Impulse responses can have very different total energy; normalizing them keeps the loudness of scrapes approximately constant across impulse responses, for example after calling SetImpulseResponse().
         /// </summary>
-        private readonly MedianFilter medianFilter = new MedianFilter(5);
+        public static bool normalizeImpulseResponse;
         /// <summary>
-        /// A linear space vector used for scrape synthesis.
+        /// The ID of this scrape event. This is used to track an ongoing scrape.
         /// </summary>
-        private static readonly double[] ScrapeLinearSpace = LinSpace.Get(0.0, 1.0, SAMPLES_LENGTH);
+        public readonly int scrapeId;
 
 
         /// <summary>
@@ -80,9 +95,22 @@ namespace Clatter.Core
         /// <param name="primary">The primary object (the smaller, moving object).</param>
         /// <param name="secondary">The secondary object (the scrape surface).</param>
         /// <param name="rng">The random number generator.</param>
-        public Scrape(ScrapeMaterial scrapeMaterial, ClatterObjectData primary, ClatterObjectData secondary, Random rng) : base(primary, secondary, rng)
+        public Scrape(ScrapeMaterial scrapeMaterial, ClatterObjectData primary, ClatterObjectData secondary, Random rng) : this(ScrapeMaterialData.Get(scrapeMaterial), primary, secondary, rng)
+        {
+        }
+
+
+        /// <summary>
+        /// (constructor)
+        ///
+        /// Use this constructor to generate scrape audio from custom surface data rather than from a `ScrapeMaterial`. The d2sdx2 array must be one element shorter than the dsdx array, and should be its derivative.
+        /// </summary>
+        /// <param name="scrapeMaterialData">The scrape surface data.</param>
+        /// <param name="primary">The primary object (the smaller, moving object).</param>
+        /// <param name="secondary">The secondary object (the scrape surface).</param>
+        /// <param name="rng">The random number generator.</param>
+        public Scrape(ScrapeMaterialData scrapeMaterialData, ClatterObjectData primary, ClatterObjectData secondary, Random rng) : base(scrapeMaterialData, primary, secondary, rng)
         {
-            scrapeMaterialData = ScrapeMaterialData.Get(scrapeMaterial);
             scrapeId = rng.Next();
         }
 
@@ -92,81 +120,192 @@ namespace Clatter.Core
         /// </summary>
         /// <param name="speed">The collision speed in meters per second.</param>
         public override bool GetAudio(double speed)
+        {
+            return GetAudio(speed, maxSpeed, scrapeAmp, null);
+        }
+
+
+        /// <summary>
+        /// Calculate the force that GetAudio() would convolve with the impulse response, without convolving it. This can be useful for visualizing the force to understand why a scrape sounds the way it does. Returns the length of the force, or 0 if the speed is too slow or too fast to calculate a force.
+        ///
+        /// Like GetAudio(), this advances the scrape along the surface and updates the median filter, meaning that a subsequent call to GetAudio() will continue from the end of this force.
+        /// </summary>
+        /// <param name="speed">The collision speed in meters per second.</param>
+        /// <param name="output">The force. This will be resized if needed.</param>
+        public int GetForce(double speed, ref double[] output)
         {
             double scrapeSpeed = Math.Min(speed, maxSpeed);
-            int numPts = (int)(Math.Floor((scrapeSpeed / 10) / ScrapeMaterialData.SCRAPE_M_PER_PIXEL) + 1);
+            int numPts = GetNumPoints(scrapeSpeed);
             if (numPts <= 1 || numPts >= scrapeMaterialData.d2sdx2.Length)
             {
-                return false;
+                return 0;
             }
-            // Get impulse response of the colliding objects.
-            if (!gotImpulseResponse)
+            ComputeForce(scrapeSpeed / maxSpeed, numPts, null);
+            if (output.Length < length)
             {
-                int impulseResponseLength = GetImpulseResponse(AdjustModes(speed), ref impulseResponse);
-                if (impulseResponseLength == 0)
-                {
-                    return false;
-                }
-                gotImpulseResponse = true;
-            }
-            // Get the final index.
-            int finalIndex = scrapeIndex + numPts;
-            // Define a linear space.
-            LinSpace.GetInPlace(0.0, 1.0, numPts, ref linearSpace);
-            // Reset the indices if they exceed the scrape surface.
-            if (finalIndex >= scrapeMaterialData.dsdx.Length)
-            {
-                scrapeIndex = 0;
-                finalIndex = numPts;
-            }
-            // Calculate the force by adding the horizontal force and the vertical force.
-            // The horizontal force is the interpolation of the dsdx array multiplied by a factor.
-            // The vertical force is a median filter sample of tanh of (the interpolation of the d2sdx2 array multiplied by a factor).
-            int horizontalInterpolationIndex = 0;
-            int verticalInterpolationIndex = 0;
-            double vertical = 0.5 * Math.Pow(scrapeSpeed / maxSpeed, 2);
-            double horizontal = 0.05 * (scrapeSpeed / maxSpeed);
-            double curveMass = 10 * primary.mass;
-            for (int i = 0; i < SAMPLES_LENGTH; i++)
-            {
-                force[i] = (horizontal * ScrapeLinearSpace[i].Interpolate1D(linearSpace, scrapeMaterialData.dsdx, 
-                    scrapeMaterialData.dsdx[scrapeIndex], scrapeMaterialData.dsdx[finalIndex], scrapeIndex, 
-                    ref horizontalInterpolationIndex, numPts)) + 
-                           (vertical * medianFilter.ProcessSample(Math.Tanh(ScrapeLinearSpace[i].Interpolate1D(linearSpace, 
-                               scrapeMaterialData.d2sdx2, scrapeMaterialData.d2sdx2[scrapeIndex],
-                               scrapeMaterialData.d2sdx2[finalIndex], scrapeIndex, 
-                               ref verticalInterpolationIndex, numPts) / curveMass)));
-            }
-            // Convolve.
-            impulseResponse.Convolve(force, SAMPLES_LENGTH, ref samples.samples);
-            // Apply roughness and amp.
-            double a = scrapeMaterialData.roughnessRatio * simulationAmp * scrapeAmp;
-            for (int i = 0; i < SAMPLES_LENGTH; i++)
-            {
-                samples.samples[i] *= a;
-            }
-            samples.length = SAMPLES_LENGTH;
-            scrapeIndex = finalIndex;
+                Array.Resize(ref output, length);
+            }
+            Buffer.BlockCopy(force, 0, output, 0, length * 8);
+            return length;
+        }
+
+
+        /// <summary>
+        /// Generate audio and add it to the existing contents of an output array. This is useful for mixing many scrape contacts into one shared output. Returns true if audio was generated. This will also set the `samples` field.
+        /// </summary>
+        /// <param name="speed">The collision speed in meters per second.</param>
+        /// <param name="output">The output audio samples. This array will be modified.</param>
+        /// <param name="outputLength">The length of the output audio data. Samples that would be added past the end of the output data are discarded.</param>
+        /// <param name="offset">The index in the output at which to start adding the audio.</param>
+        public bool AddAudio(double speed, double[] output, int outputLength, int offset = 0)
+        {
+            if (!GetAudio(speed))
+            {
+                return false;
+            }
+            Mixer.MixInto(output, outputLength, samples.samples, samples.length, offset, 1);
             return true;
         }
 
 
         /// <summary>
-        /// Returns the number of scrape events given a duration.
+        /// Use a registered impulse response instead of generating one from the modes of the colliding objects. See: `ImpulseResponseCache`. This can be called at any time; subsequent calls to GetAudio() will use the new impulse response.
         /// </summary>
-        /// <param name="duration">The duration of the scrape in seconds.</param>
-        public static int GetNumScrapeEvents(double duration)
+        /// <param name="id">The ID of the registered impulse response.</param>
+        public void SetImpulseResponse(uint id)
         {
-            return (int)(duration * Globals.framerate / SAMPLES_LENGTH);
+            double[] ir;
+            if (!ImpulseResponseCache.TryGet(id, out ir))
+            {
+                throw new Exception("Impulse response isn't registered: " + id);
+            }
+            // The cached array is shared, not copied. This is safe because the impulse response is never modified once it has been generated.
+            SetImpulseResponse(ir);
         }
-        
+
+
+        /// <summary>
+        /// Try to generate audio. Unlike GetAudio(), the sizes of the surface data are validated before generating audio, and the returned status indicates why audio wasn't generated. If the status isn't SynthesisStatus.ok, the reason is written to the debug log.
+        /// </summary>
+        /// <param name="speed">The collision speed in meters per second.</param>
+        public SynthesisStatus TryGetAudio(double speed)
+        {
+            if (!speed.IsFinite())
+            {
+                Debug.WriteLine("Scrape speed isn't finite: " + speed);
+                return SynthesisStatus.non_finite;
+            }
+            // The surface data is public and might have been replaced since this scrape was created.
+            if (scrapeMaterialData.dsdx == null || scrapeMaterialData.d2sdx2 == null)
+            {
+                Debug.WriteLine("Scrape material data is missing dsdx and/or d2sdx2.");
+                return SynthesisStatus.out_of_bounds;
+            }
+            if (scrapeMaterialData.d2sdx2.Length != scrapeMaterialData.dsdx.Length - 1)
+            {
+                Debug.WriteLine("Scrape material data d2sdx2 must be one element shorter than dsdx: " + scrapeMaterialData.dsdx.Length + ", " + scrapeMaterialData.d2sdx2.Length);
+                return SynthesisStatus.out_of_bounds;
+            }
+            int numPts = GetNumPoints(Math.Min(speed, maxSpeed));
+            if (numPts <= 1)
+            {
+                Debug.WriteLine("Scrape speed is too slow to move along the surface: " + speed);
+                return SynthesisStatus.empty_input;
+            }
+            if (numPts >= scrapeMaterialData.d2sdx2.Length)
+            {
+                Debug.WriteLine("The number of scrape surface points per block is greater than or equal to the length of the surface: " + numPts + ", " + scrapeMaterialData.d2sdx2.Length);
+                return SynthesisStatus.out_of_bounds;
+            }
+            return GetAudio(speed) ? SynthesisStatus.ok : SynthesisStatus.error;
+        }
+
+
+        /// <summary>
+        /// Generate audio for many independent scrapes in parallel. This is useful if there are many simultaneous scrape contacts. Each scrape has its own state (surface index, median filter, etc.), so the scrapes don't affect each other. After this method returns, read each scrape's `samples` field.
+        ///
+        /// If a scrape throws an exception, the remaining scrapes might not generate audio and the first exception is rethrown on the calling thread.
+        /// </summary>
+        /// <param name="scrapes">The scrapes. Each element must be a different Scrape.</param>
+        /// <param name="speeds">The collision speed of each scrape in meters per second.</param>
+        /// <param name="count">The number of scrapes (can be less than the true length of the arrays).</param>
+        /// <param name="generated">Each element will be set to true if the corresponding scrape generated audio. This will be resized if needed.</param>
+        /// <param name="maxThreads">The maximum number of threads. If this is less than 1, Environment.ProcessorCount is used.</param>
+        public static void GetAudio(Scrape[] scrapes, double[] speeds, int count, ref bool[] generated, int maxThreads = 0)
+        {
+            if (scrapes.Length < count)
+            {
+                throw new Exception("There are fewer scrapes than the count: " + scrapes.Length + ", " + count);
+            }
+            if (speeds.Length < count)
+            {
+                throw new Exception("There are fewer speeds than scrapes: " + speeds.Length + ", " + count);
+            }
+            if (generated.Length < count)
+            {
+                Array.Resize(ref generated, count);
+            }
+            int numThreads = Math.Min(maxThreads < 1 ? Environment.ProcessorCount : maxThreads, count);
+            if (numThreads <= 1)
+            {
+                for (int i = 0; i < count; i++)
+                {
+                    generated[i] = scrapes[i].GetAudio(speeds[i]);
+                }
+                return;
+            }
+            // Each thread generates audio for every numThreads-th scrape.
+            bool[] g = generated;
+            ExceptionDispatchInfo exception = null;
+            object exceptionLock = new object();
+            Thread[] threads = new Thread[numThreads];
+            for (int i = 0; i < numThreads; i++)
+            {
+                // Get a thread-safe index.
+                int start = i;
+                threads[i] = new Thread(() =>
+                {
+                    try
+                    {
+                        // Stop if another thread threw an exception.
+                        for (int j = start; j < count && exception == null; j += numThreads)
+                        {
+                            g[j] = scrapes[j].GetAudio(speeds[j]);
+                        }
+                    }
+                    catch (Exception e)
+                    {
+                        // Store the first exception so that it can be rethrown on the calling thread.
+                        lock (exceptionLock)
+                        {
+                            if (exception == null)
+                            {
+                                exception = ExceptionDispatchInfo.Capture(e);
+                            }
+                        }
+                    }
+                });
+                threads[i].Start();
+            }
+            // Wait for the threads to finish.
+            for (int i = 0; i < numThreads; i++)
+            {
+                threads[i].Join();
+            }
+            if (exception != null)
+            {
+                exception.Throw();
+            }
+        }
+
 
         /// <summary>
-        /// Returns the default size of the samples.samples array.
+        /// Returns the number of scrape events given a duration. This assumes that the scrape's samples length is samplesLength.
         /// </summary>
-        protected override int GetSamplesSize()
+        /// <param name="duration">The duration of the scrape in seconds.</param>
+        public static int GetNumScrapeEvents(double duration)
         {
-            return SAMPLES_LENGTH;
+            return (int)(duration * Globals.framerate / samplesLength);
         }
     }
 }
\ No newline at end of file
@@ -14,6 +14,10 @@ namespace Clatter.Core
         /// </summary>
         public const double SCRAPE_M_PER_PIXEL = 1394.068 * 10e-9;
         /// <summary>
+        /// The roughness ratio of generated surface data per meter of roughness.
+        /// </summary>
+        private const double GENERATED_ROUGHNESS_RATIO = 6000;
+        /// <summary>
         /// An exponent for each scrape material's roughness ratio. A lower value will cause all scrape audio to be louder relative to impact audio.
         /// </summary>
         public static double roughnessRatioExponent = 0.7;
@@ -50,24 +54,139 @@ namespace Clatter.Core
         /// Scrape data per material type.
         /// </summary>
         private static readonly Dictionary<ScrapeMaterial, ScrapeMaterialData> ScrapeMaterials = new Dictionary<ScrapeMaterial, ScrapeMaterialData>();
+        /// <summary>
+        /// A lock for ScrapeMaterials.
+        /// </summary>
+        private static readonly object ScrapeMaterialsLock = new object();
         
 
         /// <summary>
-        /// Load scrape material data from a file relative to this assembly.
+        /// Load scrape material data from a file relative to this assembly. This is thread-safe.
         /// </summary>
         /// <param name="scrapeMaterial">The scrape material.</param>
         public static void Load(ScrapeMaterial scrapeMaterial)
         {
-            // We already loaded the material.
-            if (ScrapeMaterials.ContainsKey(scrapeMaterial))
+            lock (ScrapeMaterialsLock)
+            {
+                // We already loaded the material.
+                if (ScrapeMaterials.ContainsKey(scrapeMaterial))
+                {
+                    return;
+                }
+                // Load the raw byte data.
+                byte[] raw = Loader.Load("ScrapeMaterials." + scrapeMaterial + ".bytes"); 
+                // Get the surface array.
+                double[] surface = new double[(raw.Length - 8) / 8];
+                Buffer.BlockCopy(raw, 8, surface, 0, raw.Length - 8);
+                ScrapeMaterialData scrapeMaterialData = FromSurface(surface, BitConverter.ToDouble(raw, 0));
+                ScrapeMaterials.Add(scrapeMaterial, scrapeMaterialData);
+            }
+        }
+
+
+        /// <summary>
+        /// Returns procedurally generated scrape material data. Use this to generate scrape audio without measured surface data.
+        ///
+        /// The surface is correlated Gaussian noise (a first-order autoregressive process). dsdx and d2sdx2 are derived from the surface in the same way as the built-in scrape materials, meaning that d2sdx2 is the derivative of dsdx.
+        /// </summary>
+        /// <param name="numPoints">The number of points on the surface. This must be at least 4. The built-in scrape materials have approximately 100,000 points.</param>
+        /// <param name="roughness">The standard deviation of the surface height in meters. The built-in scrape materials range from approximately 1e-6 (smooth) to 6e-6 (rough).</param>
+        /// <param name="rng">The random number generator. To generate a reproducible surface, use a seeded random number generator.</param>
+        /// <param name="correlation">The correlation between adjacent points on the surface (0 to less than 1). Higher values create a smoother, more gradually varying surface.</param>
+        public static ScrapeMaterialData Generate(int numPoints, double roughness, Random rng, double correlation = 0.998)
+        {
+            if (numPoints < 4)
+            {
+                throw new Exception("Invalid number of surface points: " + numPoints);
+            }
+            if (!(correlation >= 0 && correlation < 1))
+            {
+                throw new Exception("Invalid surface correlation: " + correlation);
+            }
+            double[] surface = new double[numPoints];
+            double innovation = Math.Sqrt(1 - correlation * correlation);
+            surface[0] = NormalDistribution.Random(0, roughness, rng);
+            for (int i = 1; i < numPoints; i++)
+            {
+                surface[i] = correlation * surface[i - 1] + innovation * NormalDistribution.Random(0, roughness, rng);
+            }
+            // This approximately matches the relationship between roughness and the roughness ratio of the built-in materials.
+            return FromSurface(surface, roughness * GENERATED_ROUGHNESS_RATIO);
+        }
+
+
+        /// <summary>
+        /// Returns procedurally generated scrape material data with a given spectral slope. Use this to generate scrape audio without measured surface data. The spectral slope controls the perceived coarseness of the surface.
+        ///
+        /// The surface is generated in the frequency domain: the power at frequency f is proportional to 1 / f^slope and the phases are random. A slope of 0 is white noise (a fine, gritty surface), 1 is pink noise, and 2 is brown noise (a coarse, gradually varying surface). dsdx and d2sdx2 are derived from the surface in the same way as the built-in scrape materials, meaning that d2sdx2 is the derivative of dsdx.
+        /// </summary>
+        /// <param name="numPoints">The number of points on the surface. This must be at least 4. The built-in scrape materials have approximately 100,000 points.</param>
+        /// <param name="roughness">The standard deviation of the surface height in meters. The built-in scrape materials range from approximately 1e-6 (smooth) to 6e-6 (rough).</param>
+        /// <param name="rng">The random number generator. To generate a reproducible surface, use a seeded random number generator.</param>
+        /// <param name="slope">The spectral slope. This must be at least 0.</param>
+        public static ScrapeMaterialData GenerateSpectral(int numPoints, double roughness, Random rng, double slope)
+        {
+            if (numPoints < 4)
+            {
+                throw new Exception("Invalid number of surface points: " + numPoints);
+            }
+            if (!(slope >= 0))
+            {
+                throw new Exception("Invalid spectral slope: " + slope);
+            }
+            int n = Fft.GetSize(numPoints);
+            double[] real = new double[n];
+            double[] imaginary = new double[n];
+            // Set random phases with amplitudes that follow the spectral slope. The spectrum is conjugate-symmetric so that the surface is real. The DC bin is 0 so that the surface has a mean of 0.
+            for (int k = 1; k <= n / 2; k++)
             {
-                return;
+                double amplitude = Math.Pow(k, -slope / 2);
+                double phase = rng.NextDouble() * 2 * Math.PI;
+                real[k] = amplitude * Math.Cos(phase);
+                imaginary[k] = amplitude * Math.Sin(phase);
+                if (k < n / 2)
+                {
+                    real[n - k] = real[k];
+                    imaginary[n - k] = -imaginary[k];
+                }
+                // The Nyquist bin must be real.
+                else
+                {
+                    imaginary[k] = 0;
+                }
             }
-            // Load the raw byte data.
-            byte[] raw = Loader.Load("ScrapeMaterials." + scrapeMaterial + ".bytes"); 
-            // Get the surface array.
-            double[] surface = new double[(raw.Length - 8) / 8];
-            Buffer.BlockCopy(raw, 8, surface, 0, raw.Length - 8);
+            Fft.Transform(real, imaginary, n, true);
+            // Scale the surface so that its standard deviation is the roughness.
+            double mean = 0;
+            for (int i = 0; i < numPoints; i++)
+            {
+                mean += real[i];
+            }
+            mean /= numPoints;
+            double variance = 0;
+            for (int i = 0; i < numPoints; i++)
+            {
+                variance += (real[i] - mean) * (real[i] - mean);
+            }
+            double standardDeviation = Math.Sqrt(variance / numPoints);
+            double scale = standardDeviation > 0 ? roughness / standardDeviation : 0;
+            double[] surface = new double[numPoints];
+            for (int i = 0; i < numPoints; i++)
+            {
+                surface[i] = (real[i] - mean) * scale;
+            }
+            // This approximately matches the relationship between roughness and the roughness ratio of the built-in materials.
+            return FromSurface(surface, roughness * GENERATED_ROUGHNESS_RATIO);
+        }
+
+
+        /// <summary>
+        /// Returns scrape material data derived from surface data.
+        /// </summary>
+        /// <param name="surface">The surface heights in meters.</param>
+        /// <param name="roughnessRatio">The roughness ratio before it is raised to roughnessRatioExponent.</param>
+        private static ScrapeMaterialData FromSurface(double[] surface, double roughnessRatio)
+        {
             // Get first-derivative data.
             double[] dsdx = new double[surface.Length - 1];
             for (int i = 1; i < surface.Length; i++)
@@ -81,23 +200,46 @@ namespace Clatter.Core
             {
                 d2sdx2[i - 1] = (dsdx[i] - dsdx[i - 1]) / SCRAPE_M_PER_PIXEL;
             }
-            ScrapeMaterialData scrapeMaterialData = new ScrapeMaterialData
+            return new ScrapeMaterialData
             {
                 dsdx = dsdx,
                 d2sdx2 = d2sdx2,
-                roughnessRatio = Math.Pow(BitConverter.ToDouble(raw, 0), roughnessRatioExponent)
+                roughnessRatio = Math.Pow(roughnessRatio, roughnessRatioExponent)
             };
-            ScrapeMaterials.Add(scrapeMaterial, scrapeMaterialData);
         }
 
 
         /// <summary>
-        /// Returns the data associated with the scrape material.
+        /// Returns the data associated with the scrape material. This is thread-safe.
         /// </summary>
         /// <param name="scrapeMaterial">The scrape material.</param>
         public static ScrapeMaterialData Get(ScrapeMaterial scrapeMaterial)
         {
-            return ScrapeMaterials[scrapeMaterial];
+            lock (ScrapeMaterialsLock)
+            {
+                return ScrapeMaterials[scrapeMaterial];
+            }
+        }
+
+
+
+        /// <summary>
+        /// Throws an exception if this data can't be used to generate audio. Scrape synthesis assumes that dsdx and d2sdx2 aren't null, that d2sdx2 is one element shorter than dsdx, and that d2sdx2 has at least two elements. Checking this once before synthesis means that malformed data fails with a clear error instead of an index exception partway through generating audio.
+        /// </summary>
+        public void Validate()
+        {
+            if (dsdx == null || d2sdx2 == null)
+            {
+                throw new Exception("Scrape material data is missing dsdx and/or d2sdx2.");
+            }
+            if (d2sdx2.Length != dsdx.Length - 1)
+            {
+                throw new Exception("Scrape material data d2sdx2 must be one element shorter than dsdx: " + dsdx.Length + ", " + d2sdx2.Length);
+            }
+            if (d2sdx2.Length < 2)
+            {
+                throw new Exception("Scrape material data is too short: " + d2sdx2.Length);
+            }
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,77 @@
+﻿using System;
+
+
+namespace Clatter.Core
+{
+    /// <summary>
+    /// Convolve a continuous stream of audio blocks with a kernel using the overlap-add method.
+    ///
+    /// Convolving each block independently discards the convolution "tail" (the final kernel.Length - 1 samples), which can cause audible clicks at block boundaries. A StreamingConvolver retains the tail and adds it to the head of the next block.
+    ///
+    /// This is useful for continuous events such as a `Scrape`, in which each block is convolved with the same impulse response. See: Scrape.overlapAdd.
+    /// </summary>
+    public class StreamingConvolver
+    {
+        /// <summary>
+        /// The convolution kernel.
+        /// </summary>
+        private readonly double[] kernel;
+        /// <summary>
+        /// The retained convolution tail. This will be added to the head of the next block.
+        /// </summary>
+        private readonly double[] tail;
+        /// <summary>
+        /// A cached array of the full convolution of a block.
+        /// </summary>
+        private double[] convolved = new double[Globals.DEFAULT_SAMPLES_LENGTH];
+
+
+        /// <summary>
+        /// (constructor)
+        /// </summary>
+        /// <param name="kernel">The convolution kernel, for example an impulse response. This array is copied.</param>
+        /// <param name="kernelLength">The length of the kernel (can be less than the true length of the array).</param>
+        public StreamingConvolver(double[] kernel, int kernelLength)
+        {
+            this.kernel = new double[kernelLength];
+            Buffer.BlockCopy(kernel, 0, this.kernel, 0, kernelLength * 8);
+            tail = new double[Math.Max(kernelLength - 1, 0)];
+        }
+
+
+        /// <summary>
+        /// Convolve a block of samples. The retained tail of the previous blocks is added to the head of the output, and the tail of this block is retained for the next block.
+        /// </summary>
+        /// <param name="input">The input block.</param>
+        /// <param name="inputLength">The length of the input block (can be less than the true length of the array). The output will have the same length.</param>
+        /// <param name="output">The output array. This will be resized if needed.</param>
+        public void ProcessBlock(double[] input, int inputLength, ref double[] output)
+        {
+            if (output.Length < inputLength)
+            {
+                Array.Resize(ref output, inputLength * 2);
+            }
+            int fullLength = inputLength + tail.Length;
+            kernel.ConvolveFft(kernel.Length, input, inputLength, fullLength, ref convolved);
+            // Add the previous tail to the head of the output.
+            for (int i = 0; i < inputLength; i++)
+            {
+                output[i] = convolved[i] + (i < tail.Length ? tail[i] : 0);
+            }
+            // Retain the new tail. If the kernel is longer than the block, some of the previous tail is carried over as well.
+            for (int i = 0; i < tail.Length; i++)
+            {
+                tail[i] = convolved[inputLength + i] + (inputLength + i < tail.Length ? tail[inputLength + i] : 0);
+            }
+        }
+
+
+        /// <summary>
+        /// Clear the retained tail. Call this before reusing the StreamingConvolver for a new stream.
+        /// </summary>
+        public void Reset()
+        {
+            Array.Clear(tail, 0, tail.Length);
+        }
+    }
+}
\ No newline at end of file
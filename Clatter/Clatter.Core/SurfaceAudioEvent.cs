@@ -0,0 +1,356 @@
+﻿using System;
+
+
+namespace Clatter.Core
+{
+    /// <summary>
+    /// SurfaceAudioEvent is an abstract base class for continuous audio events that move along a scrape surface. See: `Scrape` and `Roll`.
+    ///
+    /// The force is calculated from the surface data the same way for every SurfaceAudioEvent, using the scrape options such as Scrape.interpolationMode, Scrape.saturation, and Scrape.circularSurface. A subclass can optionally modulate the force per sample.
+    /// </summary>
+    public abstract class SurfaceAudioEvent : AudioEvent
+    {
+        /// <summary>
+        /// The default impulse response length.
+        /// </summary>
+        private const int DEFAULT_IMPULSE_RESPONSE_LENGTH = 9000;
+
+
+        /// <summary>
+        /// The length of the samples generated by this event. See: Scrape.samplesLength.
+        /// </summary>
+        protected readonly int length = Scrape.samplesLength;
+        /// <summary>
+        /// The surface data for this event.
+        /// </summary>
+        protected readonly ScrapeMaterialData scrapeMaterialData;
+        /// <summary>
+        /// A cached buffer for the force.
+        /// </summary>
+        protected readonly double[] force;
+        /// <summary>
+        /// The cached impulse response array.
+        /// </summary>
+        private double[] impulseResponse = new double[DEFAULT_IMPULSE_RESPONSE_LENGTH];
+        /// <summary>
+        /// If true, we've generated the impulse response.
+        /// </summary>
+        private bool gotImpulseResponse;
+        /// <summary>
+        /// The reciprocal of the L2 norm of the impulse response, or 1 if the impulse response is silent. See: Scrape.normalizeImpulseResponse.
+        /// </summary>
+        private double impulseResponseGain = 1;
+        /// <summary>
+        /// The overlap-add convolver of the impulse response. This is created when it is first needed. See: Scrape.overlapAdd.
+        /// </summary>
+        private StreamingConvolver streamingConvolver;
+        /// <summary>
+        /// The previous index in the surface array.
+        /// </summary>
+        private int surfaceIndex;
+        /// <summary>
+        /// The amp of the previous block of audio. This is negative if there is no previous block.
+        /// </summary>
+        private double previousAmp = -1;
+        /// <summary>
+        /// The cached linear space array. The length of this can change depending on the speed of the event.
+        /// </summary>
+        private double[] linearSpace = new double[DEFAULT_IMPULSE_RESPONSE_LENGTH];
+        /// <summary>
+        /// A cached median filter used for smoothing over the sound.
+        /// </summary>
+        private readonly MedianFilter medianFilter = new MedianFilter(Scrape.medianFilterWindowSize, Scrape.adaptiveMedianFilter);
+        /// <summary>
+        /// A cached filter used to remove DC offset. See: Scrape.removeDcOffset.
+        /// </summary>
+        private readonly DcBlocker dcBlocker = new DcBlocker();
+        /// <summary>
+        /// A linear space vector used for surface synthesis.
+        /// </summary>
+        private readonly double[] surfaceLinearSpace;
+        /// <summary>
+        /// A cached window of the dsdx array that wraps around the end of the surface. See: Scrape.circularSurface.
+        /// </summary>
+        private double[] dsdxWindow = new double[0];
+        /// <summary>
+        /// A cached window of the d2sdx2 array that wraps around the end of the surface. See: Scrape.circularSurface.
+        /// </summary>
+        private double[] d2sdx2Window = new double[0];
+
+
+        /// <summary>
+        /// Generate a surface audio event from surface data and object data.
+        /// </summary>
+        /// <param name="scrapeMaterialData">The surface data.</param>
+        /// <param name="primary">The primary object.</param>
+        /// <param name="secondary">The secondary object (the surface).</param>
+        /// <param name="rng">The random number generator.</param>
+        protected SurfaceAudioEvent(ScrapeMaterialData scrapeMaterialData, ClatterObjectData primary, ClatterObjectData secondary, Random rng) : base(primary, secondary, rng)
+        {
+            if (length < 2)
+            {
+                throw new Exception("Invalid samples length: " + length);
+            }
+            scrapeMaterialData.Validate();
+            this.scrapeMaterialData = scrapeMaterialData;
+            force = new double[length];
+            surfaceLinearSpace = LinSpace.Get(0.0, 1.0, length);
+        }
+
+
+        /// <summary>
+        /// Reset the continuation state of this event (the position on the surface and the filters) so that the next call to GetAudio() starts a new, independent event. The impulse response is kept.
+        /// </summary>
+        public virtual void Reset()
+        {
+            surfaceIndex = 0;
+            previousAmp = -1;
+            medianFilter.Reset();
+            dcBlocker.Reset();
+            if (streamingConvolver != null)
+            {
+                streamingConvolver.Reset();
+            }
+        }
+
+
+        /// <summary>
+        /// Generate audio. Returns true if audio was generated. This will set the `samples` field.
+        /// </summary>
+        /// <param name="speed">The speed in meters per second.</param>
+        /// <param name="maxSpeed">The speed is clamped to this maximum value in meters per second.</param>
+        /// <param name="amp">Multiply `AudioEvent.simulationAmp` and the roughness of the surface by this factor.</param>
+        /// <param name="modulation">Multiply each sample of the force by the corresponding value in this array. If null, the force isn't modulated.</param>
+        protected bool GetAudio(double speed, double maxSpeed, double amp, double[] modulation)
+        {
+            double clampedSpeed = Math.Min(speed, maxSpeed);
+            int numPts = GetNumPoints(clampedSpeed);
+            if (numPts <= 1 || numPts >= scrapeMaterialData.d2sdx2.Length)
+            {
+                return false;
+            }
+            // Get impulse response of the colliding objects.
+            if (!gotImpulseResponse)
+            {
+                int impulseResponseLength = GetImpulseResponse(AdjustModes(speed), ref impulseResponse);
+                if (impulseResponseLength == 0)
+                {
+                    return false;
+                }
+                gotImpulseResponse = true;
+                impulseResponseGain = GetImpulseResponseGain();
+            }
+            ComputeForce(clampedSpeed / maxSpeed, numPts, modulation);
+            // Convolve.
+            if (Scrape.overlapAdd)
+            {
+                if (streamingConvolver == null)
+                {
+                    streamingConvolver = new StreamingConvolver(impulseResponse, impulseResponse.Length);
+                }
+                streamingConvolver.ProcessBlock(force, length, ref samples.samples);
+            }
+            else
+            {
+                impulseResponse.Convolve(force, length, ref samples.samples);
+            }
+            // Remove DC offset.
+            if (Scrape.removeDcOffset)
+            {
+                dcBlocker.Process(samples.samples, length);
+            }
+            // Apply roughness and amp.
+            double a = scrapeMaterialData.roughnessRatio * simulationAmp * amp;
+            if (Scrape.normalizeImpulseResponse)
+            {
+                a *= impulseResponseGain;
+            }
+            // Ramp from the previous amp to avoid a discontinuity if the amp changed.
+            Envelope.ApplyGainRamp(samples.samples, length, previousAmp < 0 ? a : previousAmp, a);
+            previousAmp = a;
+            samples.length = length;
+            return true;
+        }
+
+
+        /// <summary>
+        /// Use an impulse response instead of generating one from the modes of the colliding objects. Subsequent calls to GetAudio() will use the new impulse response.
+        /// </summary>
+        /// <param name="ir">The impulse response. This array is shared, not copied, and must not be modified.</param>
+        protected void SetImpulseResponse(double[] ir)
+        {
+            impulseResponse = ir;
+            gotImpulseResponse = true;
+            impulseResponseGain = GetImpulseResponseGain();
+            // The overlap-add convolver copies the impulse response, so it must be recreated.
+            streamingConvolver = null;
+        }
+
+
+        /// <summary>
+        /// Returns the reciprocal of the L2 norm of the impulse response, or 1 if the impulse response is silent.
+        /// </summary>
+        private double GetImpulseResponseGain()
+        {
+            double norm = impulseResponse.GetL2Norm(impulseResponse.Length);
+            return norm > 0 ? 1 / norm : 1;
+        }
+
+
+        /// <summary>
+        /// Calculate the force and advance the event along the surface.
+        /// </summary>
+        /// <param name="speedRatio">The ratio of the clamped speed to the maximum speed.</param>
+        /// <param name="numPts">The number of surface points per block of audio. See: GetNumPoints().</param>
+        /// <param name="modulation">Multiply each sample of the force by the corresponding value in this array. If null, the force isn't modulated.</param>
+        protected void ComputeForce(double speedRatio, int numPts, double[] modulation)
+        {
+            // Get the final index.
+            int finalIndex = surfaceIndex + numPts;
+            // Define a linear space.
+            LinSpace.GetInPlace(0.0, 1.0, numPts, ref linearSpace);
+            double[] dsdx = scrapeMaterialData.dsdx;
+            double[] d2sdx2 = scrapeMaterialData.d2sdx2;
+            int surfaceLength = d2sdx2.Length;
+            int offset = surfaceIndex;
+            // Handle indices that exceed the surface. d2sdx2 is one element shorter than dsdx, so both arrays can be read at finalIndex.
+            if (finalIndex >= surfaceLength)
+            {
+                if (Scrape.circularSurface)
+                {
+                    // Copy the wrapped-around section of the surface so that it can be interpolated as a contiguous array.
+                    if (dsdxWindow.Length < numPts + 1)
+                    {
+                        dsdxWindow = new double[(numPts + 1) * 2];
+                        d2sdx2Window = new double[dsdxWindow.Length];
+                    }
+                    for (int i = 0; i <= numPts; i++)
+                    {
+                        int index = (surfaceIndex + i) % surfaceLength;
+                        dsdxWindow[i] = dsdx[index];
+                        d2sdx2Window[i] = d2sdx2[index];
+                    }
+                    dsdx = dsdxWindow;
+                    d2sdx2 = d2sdx2Window;
+                    offset = 0;
+                    finalIndex %= surfaceLength;
+                }
+                // Reset the indices.
+                else
+                {
+                    surfaceIndex = 0;
+                    offset = 0;
+                    finalIndex = numPts;
+                }
+            }
+            // Calculate the force by adding the horizontal force and the vertical force.
+            // The horizontal force is the interpolation of the dsdx array multiplied by a factor.
+            // The vertical force is a median filter sample (unless bypassMedianFilter == true) of a saturation curve (by default, tanh) of (the interpolation of the d2sdx2 array multiplied by a factor).
+            // Both terms are interpolated at the same position in the linear space; neither depends on the previous contents of the force buffer.
+            int horizontalInterpolationIndex = 0;
+            int verticalInterpolationIndex = 0;
+            if (Scrape.smoothSpeed)
+            {
+                speedRatio = speedRatio.Clamp(0, 1);
+                speedRatio = speedRatio * speedRatio * (3 - 2 * speedRatio);
+            }
+            double vertical = 0.5 * Math.Pow(speedRatio, 2);
+            double horizontal = 0.05 * speedRatio;
+            double curveMass = 10 * primary.mass;
+            // Normalize the saturation curve so that a saturation of 1 is tanh(x).
+            double saturation = Scrape.saturation;
+            double tanh1 = Math.Tanh(1);
+            double saturationGain = saturation > 0 ? tanh1 / Math.Tanh(saturation) : tanh1;
+            double saturationScale = saturation > 0 ? saturation : 0;
+            bool cubic = Scrape.interpolationMode == InterpolationMode.cubic;
+            bool bypass = Scrape.bypassMedianFilter;
+            // The oversampled points are evenly spaced around each point in the linear space. Averaging them is a box filter, not a band-limited decimation filter.
+            int oversampling = Scrape.saturationOversampling;
+            double oversamplingStep = 1.0 / ((length - 1) * oversampling);
+            double oversamplingCenter = (oversampling - 1) / 2.0;
+            int oversampledInterpolationIndex = 0;
+            double v;
+            for (int i = 0; i < length; i++)
+            {
+                if (oversampling > 1)
+                {
+                    v = 0;
+                    for (int j = 0; j < oversampling; j++)
+                    {
+                        v += Saturate(Interpolate(surfaceLinearSpace[i] + (j - oversamplingCenter) * oversamplingStep,
+                            linearSpace, d2sdx2, d2sdx2[offset], d2sdx2[offset + numPts], offset,
+                            ref oversampledInterpolationIndex, numPts, cubic) / curveMass, saturationScale, saturationGain);
+                    }
+                    v /= oversampling;
+                }
+                else
+                {
+                    v = Saturate(Interpolate(surfaceLinearSpace[i], linearSpace, d2sdx2, d2sdx2[offset],
+                        d2sdx2[offset + numPts], offset, ref verticalInterpolationIndex, numPts, cubic) / curveMass,
+                        saturationScale, saturationGain);
+                }
+                if (!bypass)
+                {
+                    v = medianFilter.ProcessSample(v);
+                }
+                force[i] = (horizontal * Interpolate(surfaceLinearSpace[i], linearSpace, dsdx,
+                    dsdx[offset], dsdx[offset + numPts], offset,
+                    ref horizontalInterpolationIndex, numPts, cubic)) + vertical * v;
+                if (modulation != null)
+                {
+                    force[i] *= modulation[i];
+                }
+            }
+            surfaceIndex = finalIndex;
+        }
+
+
+        /// <summary>
+        /// Returns the number of surface points per block of audio. The distance travelled along the surface is proportional to the length of the samples.
+        /// </summary>
+        /// <param name="clampedSpeed">The clamped speed in meters per second.</param>
+        protected int GetNumPoints(double clampedSpeed)
+        {
+            return (int)(Math.Floor((clampedSpeed / 10) * length / Scrape.SAMPLES_LENGTH / ScrapeMaterialData.SCRAPE_M_PER_PIXEL) + 1);
+        }
+
+
+        /// <summary>
+        /// Returns the default size of the samples.samples array.
+        /// </summary>
+        protected override int GetSamplesSize()
+        {
+            return length;
+        }
+
+
+        /// <summary>
+        /// Interpolate the surface. See: Scrape.interpolationMode.
+        /// </summary>
+        /// <param name="value">The value to be calculated.</param>
+        /// <param name="x">The input data points.</param>
+        /// <param name="y">The output data points.</param>
+        /// <param name="lower">The value to be returned for values before the first point in x.</param>
+        /// <param name="upper">The value to be returned for values after the last point in x.</param>
+        /// <param name="yIndexOffset">Offset the y index by this value.</param>
+        /// <param name="startX">Start interpolating the x array at this index.</param>
+        /// <param name="endX">The final index in the x array.</param>
+        /// <param name="cubic">If true, use cubic interpolation. If false, use linear interpolation.</param>
+        private static double Interpolate(double value, double[] x, double[] y, double lower, double upper, int yIndexOffset, ref int startX, int endX, bool cubic)
+        {
+            return cubic ? value.Interpolate1DCubic(x, y, lower, upper, yIndexOffset, ref startX, endX) : value.Interpolate1D(x, y, lower, upper, yIndexOffset, ref startX, endX);
+        }
+
+
+        /// <summary>
+        /// Returns the saturated vertical force. See: Scrape.saturation.
+        /// </summary>
+        /// <param name="x">The input value.</param>
+        /// <param name="scale">The saturation, or 0 if the curve is linear.</param>
+        /// <param name="gain">The normalization gain of the curve.</param>
+        private static double Saturate(double x, double scale, double gain)
+        {
+            return scale > 0 ? Math.Tanh(scale * x) * gain : x * gain;
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,29 @@
+﻿namespace Clatter.Core
+{
+    /// <summary>
+    /// The result of an attempt to generate audio. See: ExternalEntryPoint.TryGetAudio().
+    /// </summary>
+    public enum SynthesisStatus : byte
+    {
+        /// <summary>
+        /// Audio was generated successfully.
+        /// </summary>
+        ok = 0,
+        /// <summary>
+        /// An input value, such as a material or an audio event type, is out of range.
+        /// </summary>
+        out_of_bounds = 1,
+        /// <summary>
+        /// There is nothing to generate, for example a scrape with a duration of zero.
+        /// </summary>
+        empty_input = 2,
+        /// <summary>
+        /// An input value is NaN or infinity.
+        /// </summary>
+        non_finite = 3,
+        /// <summary>
+        /// An unexpected error occurred while generating audio.
+        /// </summary>
+        error = 4
+    }
+}
\ No newline at end of file
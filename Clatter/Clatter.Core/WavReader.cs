@@ -0,0 +1,136 @@
+﻿using System;
+using System.IO;
+using System.Text;
+
+
+namespace Clatter.Core
+{
+    /// <summary>
+    /// Read audio samples from a .wav file. This can be used to load an impulse response for use with ImpulseResponseCache and Scrape.SetImpulseResponse().
+    ///
+    /// WavReader can read int16 PCM and float32 IEEE float files, including files with a WAVE_FORMAT_EXTENSIBLE header (see: `WavWriter`). Multi-channel audio is mixed down to mono.
+    /// </summary>
+    public static class WavReader
+    {
+        /// <summary>
+        /// The scale of int16 samples. This is the same scale that is used to write int16 samples, so that reading a file written by WavWriter returns the original values.
+        /// </summary>
+        private const double INT16_SCALE = 32767;
+
+
+        /// <summary>
+        /// Try to read audio samples from a .wav file and mix them down to mono. Returns false if the file doesn't exist, isn't a .wav file, or has an unsupported format, in which case length and framerate are 0.
+        ///
+        /// The samples aren't resampled. If framerate is different from Globals.framerate, call Resampler.Sinc() to resample them.
+        /// </summary>
+        /// <param name="path">The path to the .wav file.</param>
+        /// <param name="samples">The mono audio samples. This will be resized if needed.</param>
+        /// <param name="length">The number of samples.</param>
+        /// <param name="framerate">The framerate of the audio.</param>
+        public static bool TryReadMono(string path, ref double[] samples, out int length, out int framerate)
+        {
+            length = 0;
+            framerate = 0;
+            if (!File.Exists(path))
+            {
+                return false;
+            }
+            byte[] raw = File.ReadAllBytes(path);
+            if (raw.Length < 12 || GetChunkId(raw, 0) != "RIFF" || GetChunkId(raw, 8) != "WAVE")
+            {
+                return false;
+            }
+            int format = 0;
+            int channels = 0;
+            int rate = 0;
+            int bitsPerSample = 0;
+            int dataStart = -1;
+            int dataLength = 0;
+            // Read each chunk. Chunks are padded to an even number of bytes.
+            int position = 12;
+            while (position + 8 <= raw.Length)
+            {
+                string id = GetChunkId(raw, position);
+                int size = BitConverter.ToInt32(raw, position + 4);
+                int start = position + 8;
+                if (size < 0)
+                {
+                    return false;
+                }
+                if (id == "fmt ")
+                {
+                    if (size < 16 || start + size > raw.Length)
+                    {
+                        return false;
+                    }
+                    format = BitConverter.ToUInt16(raw, start);
+                    channels = BitConverter.ToUInt16(raw, start + 2);
+                    rate = BitConverter.ToInt32(raw, start + 4);
+                    bitsPerSample = BitConverter.ToUInt16(raw, start + 14);
+                    // The format code of a WAVE_FORMAT_EXTENSIBLE header is the first two bytes of the SubFormat GUID.
+                    if (format == 0xFFFE)
+                    {
+                        if (size < 40)
+                        {
+                            return false;
+                        }
+                        format = BitConverter.ToUInt16(raw, start + 24);
+                    }
+                }
+                else if (id == "data")
+                {
+                    dataStart = start;
+                    // The data size can be wrong if the file wasn't finalized, so clamp it to the end of the file.
+                    dataLength = Math.Min(size, raw.Length - start);
+                    break;
+                }
+                // The chunk size comes from the file, so make sure that it doesn't exceed the file before advancing to the next chunk.
+                if (size > raw.Length - start)
+                {
+                    break;
+                }
+                position = start + size + (size % 2);
+            }
+            // 1 = PCM, 3 = IEEE float.
+            bool int16 = format == 1 && bitsPerSample == 16;
+            bool float32 = format == 3 && bitsPerSample == 32;
+            if (dataStart < 0 || channels <= 0 || rate <= 0 || (!int16 && !float32))
+            {
+                return false;
+            }
+            int bytesPerSample = bitsPerSample / 8;
+            int frameLength = bytesPerSample * channels;
+            int numFrames = dataLength / frameLength;
+            if (samples.Length < numFrames)
+            {
+                Array.Resize(ref samples, numFrames);
+            }
+            double channelsD = channels;
+            for (int i = 0; i < numFrames; i++)
+            {
+                double sum = 0;
+                int frameStart = dataStart + i * frameLength;
+                for (int j = 0; j < channels; j++)
+                {
+                    int index = frameStart + j * bytesPerSample;
+                    sum += int16 ? BitConverter.ToInt16(raw, index) / INT16_SCALE : BitConverter.ToSingle(raw, index);
+                }
+                samples[i] = sum / channelsD;
+            }
+            length = numFrames;
+            framerate = rate;
+            return true;
+        }
+
+
+        /// <summary>
+        /// Returns a four-character chunk ID.
+        /// </summary>
+        /// <param name="raw">The raw file data.</param>
+        /// <param name="start">The start index of the chunk ID.</param>
+        private static string GetChunkId(byte[] raw, int start)
+        {
+            return Encoding.ASCII.GetString(raw, start, 4);
+        }
+    }
+}
\ No newline at end of file
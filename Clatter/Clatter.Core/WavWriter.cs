@@ -7,6 +7,10 @@ namespace Clatter.Core
     /// <summary>
     /// Write audio samples to a .wav file. Instantiate a WavWriter to begin writing audio. Call Write(data) to continuously write chunks of audio data to the file. Call End() to stop writing and append the wav header data to the file.
     ///
+    /// To write a single `Samples` object to a file, call WavWriter.WriteFile(path, samples).
+    ///
+    /// Mono and stereo files have a standard PCM (or IEEE float) header. Files with more than two channels have a WAVE_FORMAT_EXTENSIBLE header, which includes a channel mask that tells other applications which speaker each channel corresponds to.
+    ///
     /// ## Code Examples
     ///
     /// {code_example:ScrapeAudioExample}
@@ -23,13 +27,13 @@ namespace Clatter.Core
         /// </summary>
         private readonly string path;
         /// <summary>
-        /// The number of channels.
+        /// A header for a .wav file.
         /// </summary>
-        private readonly int channels;
+        private readonly byte[] wavHeader;
         /// <summary>
-        /// A header for a .wav file.
+        /// The SubFormat GUID of a WAVE_FORMAT_EXTENSIBLE header, excluding the first two bytes (the format code).
         /// </summary>
-        private static byte[] wavHeader;
+        private static readonly byte[] SubFormatGuid = new byte[] { 0x00, 0x00, 0x00, 0x00, 0x10, 0x00, 0x80, 0x00, 0x00, 0xAA, 0x00, 0x38, 0x9B, 0x71 };
         
         
         /// <summary>
@@ -38,10 +42,12 @@ namespace Clatter.Core
         /// <param name="path">The path to the output file.</param>
         /// <param name="overwrite">If true, overwrite an existing file.</param>
         /// <param name="channels">The number of audio channels.</param>
-        public WavWriter(string path, bool overwrite = true, int channels = 1)
+        /// <param name="floatingPoint">If true, the audio data is float32. If false, the audio data is int16.</param>
+        /// <param name="channelMask">If channels is greater than 2, this is the WAVE_FORMAT_EXTENSIBLE channel mask, a bitmask of the speaker positions of the channels, e.g. 0x33 for quad (front left, front right, back left, back right). If this is 0, a default mask is used for 4, 6 (5.1), and 8 (7.1) channels, and no speaker positions are assigned for other numbers of channels. This is ignored for mono and stereo audio.</param>
+        public WavWriter(string path, bool overwrite = true, int channels = 1, bool floatingPoint = false, uint channelMask = 0)
         {
             this.path = path;
-            this.channels = channels;
+            wavHeader = GetWavHeader(channels, floatingPoint, channelMask);
             // Create the directory.
             string d = Path.GetDirectoryName(Path.GetFullPath(this.path));
             if (!Directory.Exists(d))
@@ -59,13 +65,9 @@ namespace Clatter.Core
         /// <summary>
         /// Write audio samples to the .wav file.
         /// </summary>
-        /// <param name="audio">The audio data as an int16 byte array.</param>
+        /// <param name="audio">The audio data as an int16 byte array, or as a float32 byte array if this WavWriter was created with floatingPoint == true.</param>
         public void Write(byte[] audio)
         {
-            if (wavHeader == null)
-            {
-                SetWavHeader(channels);
-            }
             // Write the .wav header.
             if (!wroteWavHeader)
             {
@@ -93,34 +95,94 @@ namespace Clatter.Core
             int fileSize = (int)new FileInfo(path).Length;
             using (FileStream filestream = new FileStream(path, FileMode.Open))
             {
-                // Set the file size. 
+                // Set the RIFF chunk size, which excludes the "RIFF" ID and the size itself.
                 filestream.Seek(4, SeekOrigin.Begin);
-                filestream.Write(BitConverter.GetBytes(fileSize), 0, 4);
-                // Set the data size.
-                filestream.Seek(40, SeekOrigin.Begin);
+                filestream.Write(BitConverter.GetBytes(fileSize - 8), 0, 4);
+                // Set the data size, which is the last value in the header.
+                filestream.Seek(wavHeader.Length - 4, SeekOrigin.Begin);
                 filestream.Write(BitConverter.GetBytes(fileSize - wavHeader.Length), 0, 4);
             }
         }
 
 
         /// <summary>
-        /// Set the wav header. Source: https://docs.fileformat.com/audio/wav/
+        /// Write audio samples to a new .wav file.
+        /// </summary>
+        /// <param name="path">The path to the output file. If the file already exists, it will be overwritten.</param>
+        /// <param name="samples">The audio samples.</param>
+        /// <param name="channels">The number of audio channels.</param>
+        /// <param name="floatingPoint">If true, write float32 data. If false, write int16 data.</param>
+        /// <param name="channelMask">The channel mask. See: WavWriter(path, overwrite, channels, floatingPoint, channelMask).</param>
+        public static void WriteFile(string path, Samples samples, int channels = 1, bool floatingPoint = false, uint channelMask = 0)
+        {
+            WavWriter writer = new WavWriter(path, true, channels, floatingPoint, channelMask);
+            writer.Write(floatingPoint ? samples.ToFloatBytes() : samples.ToInt16Bytes());
+            writer.End();
+        }
+
+
+        /// <summary>
+        /// Returns a wav header. Source: https://docs.fileformat.com/audio/wav/
+        ///
+        /// If there are more than two channels, this returns a WAVE_FORMAT_EXTENSIBLE header. Source: https://learn.microsoft.com/en-us/windows/win32/api/mmreg/ns-mmreg-waveformatextensible
+        /// </summary>
+        /// <param name="channels">The number of channels.</param>
+        /// <param name="floatingPoint">If true, the audio data is float32. If false, the audio data is int16.</param>
+        /// <param name="channelMask">The channel mask. This is ignored if there are two or fewer channels.</param>
+        private static byte[] GetWavHeader(int channels, bool floatingPoint, uint channelMask)
+        {
+            // 1 = PCM, 3 = IEEE float.
+            ushort format = (ushort)(floatingPoint ? 3 : 1);
+            int bytesPerSample = floatingPoint ? 4 : 2;
+            bool extensible = channels > 2;
+            // The extensible format chunk has 24 more bytes: cbSize, the valid bits per sample, the channel mask, and the SubFormat GUID.
+            int formatChunkSize = extensible ? 40 : 16;
+            byte[] header = new byte[28 + formatChunkSize];
+            Buffer.BlockCopy(System.Text.Encoding.ASCII.GetBytes("RIFF"), 0, header, 0, 4);
+            Buffer.BlockCopy(System.Text.Encoding.ASCII.GetBytes("WAVE"), 0, header, 8, 4);
+            Buffer.BlockCopy(System.Text.Encoding.ASCII.GetBytes("fmt "), 0, header, 12, 4);
+            Buffer.BlockCopy(BitConverter.GetBytes(formatChunkSize), 0, header, 16, 4);
+            // 0xFFFE = WAVE_FORMAT_EXTENSIBLE.
+            Buffer.BlockCopy(BitConverter.GetBytes(extensible ? (ushort)0xFFFE : format), 0, header, 20, 2);
+            Buffer.BlockCopy(BitConverter.GetBytes((ushort)channels), 0, header, 22, 2);
+            Buffer.BlockCopy(BitConverter.GetBytes(Globals.framerate), 0, header, 24, 4);
+            Buffer.BlockCopy(BitConverter.GetBytes(Globals.framerate * channels * bytesPerSample), 0, header, 28, 4);
+            Buffer.BlockCopy(BitConverter.GetBytes((ushort)(channels * bytesPerSample)), 0, header, 32, 2);
+            Buffer.BlockCopy(BitConverter.GetBytes((ushort)(bytesPerSample * 8)), 0, header, 34, 2);
+            if (extensible)
+            {
+                Buffer.BlockCopy(BitConverter.GetBytes((ushort)22), 0, header, 36, 2);
+                Buffer.BlockCopy(BitConverter.GetBytes((ushort)(bytesPerSample * 8)), 0, header, 38, 2);
+                Buffer.BlockCopy(BitConverter.GetBytes(channelMask == 0 ? GetDefaultChannelMask(channels) : channelMask), 0, header, 40, 4);
+                // The SubFormat GUID starts with the format code.
+                Buffer.BlockCopy(BitConverter.GetBytes(format), 0, header, 44, 2);
+                Buffer.BlockCopy(SubFormatGuid, 0, header, 46, SubFormatGuid.Length);
+            }
+            Buffer.BlockCopy(System.Text.Encoding.ASCII.GetBytes("data"), 0, header, header.Length - 8, 4);
+            return header;
+        }
+
+
+        /// <summary>
+        /// Returns the default WAVE_FORMAT_EXTENSIBLE channel mask for a number of channels, or 0 if there is no default.
         /// </summary>
         /// <param name="channels">The number of channels.</param>
-        private static void SetWavHeader(int channels)
+        private static uint GetDefaultChannelMask(int channels)
         {
-            wavHeader = new byte[44];
-            Buffer.BlockCopy(System.Text.Encoding.ASCII.GetBytes("RIFF"), 0, wavHeader, 0, 4);
-            Buffer.BlockCopy(System.Text.Encoding.ASCII.GetBytes("WAVE"), 0, wavHeader, 8, 4);
-            Buffer.BlockCopy(System.Text.Encoding.ASCII.GetBytes("fmt "), 0, wavHeader, 12, 4);
-            Buffer.BlockCopy(BitConverter.GetBytes(16), 0, wavHeader, 16, 4);
-            Buffer.BlockCopy(BitConverter.GetBytes((ushort)1), 0, wavHeader, 20, 2);
-            Buffer.BlockCopy(BitConverter.GetBytes((ushort)channels), 0, wavHeader, 22, 2);
-            Buffer.BlockCopy(BitConverter.GetBytes(Globals.framerate), 0, wavHeader, 24, 4);
-            Buffer.BlockCopy(BitConverter.GetBytes(Globals.framerate * channels * 2), 0, wavHeader, 28, 4);
-            Buffer.BlockCopy(BitConverter.GetBytes((ushort)(channels * 2)), 0, wavHeader, 32, 2);
-            Buffer.BlockCopy(BitConverter.GetBytes((ushort)16), 0, wavHeader, 34, 2);
-            Buffer.BlockCopy(System.Text.Encoding.ASCII.GetBytes("data"), 0, wavHeader, 36, 4);
+            switch (channels)
+            {
+                // Front left, front right, back left, back right.
+                case 4:
+                    return 0x33;
+                // Front left, front right, front center, low frequency, back left, back right.
+                case 6:
+                    return 0x3F;
+                // Front left, front right, front center, low frequency, back left, back right, side left, side right.
+                case 8:
+                    return 0x63F;
+                default:
+                    return 0;
+            }
         }
     }
 }
\ No newline at end of file
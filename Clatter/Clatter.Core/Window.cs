@@ -0,0 +1,96 @@
+﻿using System;
+
+
+namespace Clatter.Core
+{
+    /// <summary>
+    /// Analysis window functions, for example to reduce spectral leakage before an FFT (see: `Fft`).
+    ///
+    /// The windows are symmetric: the first and last coefficients are equal.
+    /// </summary>
+    public static class Window
+    {
+        /// <summary>
+        /// Fill an array with Hann window coefficients. The first and last coefficients are 0.
+        /// </summary>
+        /// <param name="length">The length of the window.</param>
+        /// <param name="window">The window coefficients. This will be resized if needed.</param>
+        public static void Hann(int length, ref double[] window)
+        {
+            GetCosineSum(length, 0.5, 0.5, 0, ref window);
+        }
+
+
+        /// <summary>
+        /// Fill an array with Hamming window coefficients. The first and last coefficients are 0.08.
+        /// </summary>
+        /// <param name="length">The length of the window.</param>
+        /// <param name="window">The window coefficients. This will be resized if needed.</param>
+        public static void Hamming(int length, ref double[] window)
+        {
+            GetCosineSum(length, 0.54, 0.46, 0, ref window);
+        }
+
+
+        /// <summary>
+        /// Fill an array with Blackman window coefficients. The first and last coefficients are 0 (within floating-point error).
+        /// </summary>
+        /// <param name="length">The length of the window.</param>
+        /// <param name="window">The window coefficients. This will be resized if needed.</param>
+        public static void Blackman(int length, ref double[] window)
+        {
+            GetCosineSum(length, 0.42, 0.5, 0.08, ref window);
+        }
+
+
+        /// <summary>
+        /// Multiply audio samples by a window in-place.
+        /// </summary>
+        /// <param name="samples">The audio samples. This array will be modified.</param>
+        /// <param name="length">The length of the audio data (can be less than the true length of the array). The window must be at least this long.</param>
+        /// <param name="window">The window coefficients.</param>
+        public static void Apply(double[] samples, int length, double[] window)
+        {
+            if (window.Length < length)
+            {
+                throw new Exception("The window is shorter than the audio data: " + window.Length + ", " + length);
+            }
+            for (int i = 0; i < length; i++)
+            {
+                samples[i] *= window[i];
+            }
+        }
+
+
+        /// <summary>
+        /// Fill an array with the coefficients of a generalized cosine window: a0 - a1 * cos(2 * pi * n / (N - 1)) + a2 * cos(4 * pi * n / (N - 1)).
+        /// </summary>
+        /// <param name="length">The length of the window.</param>
+        /// <param name="a0">The constant term.</param>
+        /// <param name="a1">The first cosine coefficient.</param>
+        /// <param name="a2">The second cosine coefficient.</param>
+        /// <param name="window">The window coefficients. This will be resized if needed.</param>
+        private static void GetCosineSum(int length, double a0, double a1, double a2, ref double[] window)
+        {
+            if (length <= 0)
+            {
+                return;
+            }
+            if (window.Length < length)
+            {
+                Array.Resize(ref window, length);
+            }
+            if (length == 1)
+            {
+                window[0] = 1;
+                return;
+            }
+            double x;
+            for (int i = 0; i < length; i++)
+            {
+                x = 2 * Math.PI * i / (length - 1);
+                window[i] = a0 - a1 * Math.Cos(x) + a2 * Math.Cos(2 * x);
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,108 @@
+﻿using Clatter.Core;
+using NUnit.Framework;
+
+
+namespace Clatter.Tests
+{
+    [TestFixture]
+    public class ConvolutionTests
+    {
+        private static readonly object[] KnownConvolutions =
+        {
+            new object[] { new double[] { 1, 2, 3 }, new double[] { 1, 1 }, new double[] { 1, 3, 5, 3 } },
+            // The kernel is long enough to use the unrolled loop.
+            new object[] { new double[] { 1, 2, 3 }, new double[] { 1, 1, 1, 1, 1 }, new double[] { 1, 3, 6, 6, 6, 5, 3 } }
+        };
+
+
+        [TestCaseSource(nameof(KnownConvolutions))]
+        public void DirectConvolution(double[] a, double[] kernel, double[] expected)
+        {
+            AssertConvolution(a, kernel, expected, false);
+        }
+
+
+        [TestCaseSource(nameof(KnownConvolutions))]
+        public void UnrolledConvolution(double[] a, double[] kernel, double[] expected)
+        {
+            AssertConvolution(a, kernel, expected, true);
+        }
+
+
+        [TestCaseSource(nameof(KnownConvolutions))]
+        public void FftConvolution(double[] a, double[] kernel, double[] expected)
+        {
+            double[] result = new double[0];
+            a.ConvolveFft(a.Length, kernel, kernel.Length, expected.Length, ref result);
+            for (int i = 0; i < expected.Length; i++)
+            {
+                Assert.AreEqual(expected[i], result[i], 1e-12, "Index: " + i);
+            }
+        }
+
+
+        [TestCase(4410, 4410)]
+        [TestCase(44100, 512)]
+        [TestCase(1000, 3000)]
+        public void FftMatchesDirect(int inputLength, int kernelLength)
+        {
+            Random rng = new Random(0);
+            double[] input = GetNoise(inputLength, rng);
+            double[] kernel = GetNoise(kernelLength, rng);
+            int length = DoubleExtensions.GetConvolutionLength(inputLength, kernelLength);
+            double[] fft = new double[0];
+            input.ConvolveFft(inputLength, kernel, kernelLength, length, ref fft);
+            int fftConvolutionKernelLength = DoubleExtensions.fftConvolutionKernelLength;
+            DoubleExtensions.fftConvolutionKernelLength = int.MaxValue;
+            double[] direct = new double[0];
+            try
+            {
+                input.ConvolveFull(inputLength, kernel, kernelLength, ref direct);
+            }
+            finally
+            {
+                DoubleExtensions.fftConvolutionKernelLength = fftConvolutionKernelLength;
+            }
+            for (int i = 0; i < length; i++)
+            {
+                Assert.AreEqual(direct[i], fft[i], 1e-9, "Index: " + i);
+            }
+        }
+
+
+        private static double[] GetNoise(int length, Random rng)
+        {
+            double[] noise = new double[length];
+            for (int i = 0; i < length; i++)
+            {
+                noise[i] = rng.NextDouble() * 2 - 1;
+            }
+            return noise;
+        }
+
+
+        private static void AssertConvolution(double[] a, double[] kernel, double[] expected, bool unroll)
+        {
+            // Use direct convolution rather than FFT convolution.
+            int fftConvolutionKernelLength = DoubleExtensions.fftConvolutionKernelLength;
+            bool unrollConvolution = DoubleExtensions.unrollConvolution;
+            DoubleExtensions.fftConvolutionKernelLength = int.MaxValue;
+            DoubleExtensions.unrollConvolution = unroll;
+            try
+            {
+                double[] result = new double[0];
+                int length = a.ConvolveFull(a.Length, kernel, kernel.Length, ref result);
+                Assert.AreEqual(expected.Length, length);
+                for (int i = 0; i < expected.Length; i++)
+                {
+                    Assert.AreEqual(expected[i], result[i], "Index: " + i);
+                }
+            }
+            finally
+            {
+                DoubleExtensions.fftConvolutionKernelLength = fftConvolutionKernelLength;
+                DoubleExtensions.unrollConvolution = unrollConvolution;
+            }
+        }
+    }
+}
@@ -0,0 +1,39 @@
+﻿using Clatter.Core;
+using NUnit.Framework;
+
+
+namespace Clatter.Tests
+{
+    [TestFixture]
+    public class FloatExtensionsTests
+    {
+        // The machine epsilon of a float (2^-23).
+        private const double FLOAT_EPSILON = 1.1920929e-7;
+
+
+        [Test]
+        public void ConvolveMatchesDouble()
+        {
+            Random rng = new Random(0);
+            float[] a = new float[4410];
+            float[] kernel = new float[2000];
+            for (int i = 0; i < a.Length; i++)
+            {
+                a[i] = (float)(rng.NextDouble() * 2 - 1);
+            }
+            for (int i = 0; i < kernel.Length; i++)
+            {
+                kernel[i] = (float)(rng.NextDouble() * 2 - 1);
+            }
+            float[] result = new float[0];
+            a.Convolve(kernel, a.Length, ref result);
+            // Convolve the same values as doubles.
+            double[] expected = new double[0];
+            a.ToDoubles(a.Length).Convolve(kernel.ToDoubles(kernel.Length), a.Length, ref expected);
+            for (int i = 0; i < a.Length; i++)
+            {
+                Assert.AreEqual(expected[i], result[i], FLOAT_EPSILON * Math.Max(1, Math.Abs(expected[i])), "Index: " + i);
+            }
+        }
+    }
+}
@@ -0,0 +1,64 @@
+﻿using Clatter.Core;
+using NUnit.Framework;
+
+
+namespace Clatter.Tests
+{
+    [TestFixture]
+    public class InterpolationTests
+    {
+        // Indices in a scrape-length linear space and the expected interpolation of the ceramic d2sdx2 array at those indices (numPts = 100, offset = 1000).
+        // The expected values were calculated independently of DoubleExtensions by a bisection search of the same surface data.
+        private static readonly int[] ReferenceIndices = { 0, 1, 2, 44, 45, 46, 1000, 2205, 3333, 4408, 4409 };
+        private static readonly double[] ReferenceValues =
+        {
+            -31.94211546755945, -31.8910920286753, -31.840068589791144, -29.697084156656743, -29.661280792548126,
+            -29.643013601550454, -22.785769757354107, 10.84270836726657, 17.381931859361412, 11.043996652921518,
+            9.353982156617517
+        };
+
+
+        [Test]
+        public void LinearRamp()
+        {
+            // Interpolating a linear ramp must reproduce the ramp.
+            double[] x = LinSpace.Get(0, 1, 100);
+            double[] y = new double[x.Length];
+            for (int i = 0; i < x.Length; i++)
+            {
+                y[i] = 3 * x[i] + 2;
+            }
+            double[] values = LinSpace.Get(0, 1, Scrape.SAMPLES_LENGTH);
+            int startX = 0;
+            for (int i = 0; i < values.Length; i++)
+            {
+                double v = values[i].Interpolate1D(x, y, y[0], y[y.Length - 1], 0, ref startX, x.Length);
+                Assert.AreEqual(3 * values[i] + 2, v, 1e-12, "Value: " + values[i]);
+            }
+        }
+
+
+        [Test]
+        public void ReferenceTrace()
+        {
+            // Interpolate the scrape surface the same way that a Scrape does: one increasing sequence of values with a shared startX.
+            ScrapeMaterialData.Load(ScrapeMaterial.ceramic);
+            double[] d2sdx2 = ScrapeMaterialData.Get(ScrapeMaterial.ceramic).d2sdx2;
+            int numPts = 100;
+            int offset = 1000;
+            double[] x = LinSpace.Get(0, 1, numPts);
+            double[] values = LinSpace.Get(0, 1, Scrape.SAMPLES_LENGTH);
+            double[] trace = new double[values.Length];
+            int startX = 0;
+            for (int i = 0; i < values.Length; i++)
+            {
+                trace[i] = values[i].Interpolate1D(x, d2sdx2, d2sdx2[offset], d2sdx2[offset + numPts], offset, ref startX, numPts);
+            }
+            for (int i = 0; i < ReferenceIndices.Length; i++)
+            {
+                double expected = ReferenceValues[i];
+                Assert.AreEqual(expected, trace[ReferenceIndices[i]], 1e-9 * Math.Abs(expected), "Index: " + ReferenceIndices[i]);
+            }
+        }
+    }
+}
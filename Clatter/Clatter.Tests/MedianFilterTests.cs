@@ -0,0 +1,47 @@
+﻿using Clatter.Core;
+using NUnit.Framework;
+
+
+namespace Clatter.Tests
+{
+    [TestFixture]
+    public class MedianFilterTests
+    {
+        private const int WINDOW_SIZE = 5;
+
+
+        [Test]
+        public void AdaptiveWarmUpMatchesFixedWindow()
+        {
+            // Until the mean derivative has warmed up, an adaptive filter mustn't narrow its window.
+            MedianFilter adaptive = new MedianFilter(WINDOW_SIZE, true);
+            MedianFilter fixedWindow = new MedianFilter(WINDOW_SIZE);
+            Random rng = new Random(0);
+            for (int i = 0; i < WINDOW_SIZE; i++)
+            {
+                double sample = rng.NextDouble() * 2 - 1;
+                Assert.AreEqual(fixedWindow.ProcessSample(sample), adaptive.ProcessSample(sample), "Sample: " + i);
+            }
+        }
+
+
+        [Test]
+        public void AdaptiveRecoversFromNaN()
+        {
+            MedianFilter adaptive = new MedianFilter(WINDOW_SIZE, true);
+            Random rng = new Random(0);
+            for (int i = 0; i < 200; i++)
+            {
+                adaptive.ProcessSample(rng.NextDouble() * 0.02 - 0.01);
+            }
+            adaptive.ProcessSample(double.NaN);
+            // Wait for the NaN to leave the window.
+            for (int i = 0; i < WINDOW_SIZE * 2; i++)
+            {
+                adaptive.ProcessSample(rng.NextDouble() * 0.02 - 0.01);
+            }
+            // A large step is a transient, so the window narrows and the step is preserved.
+            Assert.AreEqual(10, adaptive.ProcessSample(10));
+        }
+    }
+}
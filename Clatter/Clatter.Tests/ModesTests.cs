@@ -0,0 +1,66 @@
+﻿using Clatter.Core;
+using NUnit.Framework;
+
+
+namespace Clatter.Tests
+{
+    [TestFixture]
+    public class ModesTests
+    {
+        private const double RESONANCE = 1;
+
+
+        [Test]
+        public void DecayRecurrenceMatchesPow()
+        {
+            // Find the mode of a built-in material with the longest decay (the most samples).
+            double frequency = 0;
+            double power = 0;
+            double decayTime = 0;
+            int longestModeCount = 0;
+            foreach (ImpactMaterial impactMaterial in Enum.GetValues(typeof(ImpactMaterial)))
+            {
+                ImpactMaterialData.Load(impactMaterial);
+                ImpactMaterialData materialData = ImpactMaterialData.impactMaterials[impactMaterial];
+                for (int i = 0; i < materialData.cf.Length; i++)
+                {
+                    // This is the number of samples calculated by Modes.
+                    int modeCount = (int)Math.Ceiling((materialData.rt[i] * 1e3 * (80.0 + materialData.op[i]) / 60.0) / 1e3 * Globals.framerate);
+                    if (modeCount > longestModeCount)
+                    {
+                        longestModeCount = modeCount;
+                        frequency = materialData.cf[i];
+                        power = materialData.op[i];
+                        decayTime = materialData.rt[i] * 1e3;
+                    }
+                }
+            }
+            double dampingSlope = Modes.dampingSlope;
+            Modes.dampingSlope = 0;
+            double[][] modes = new double[1][];
+            int[] modeLengths = new int[1];
+            try
+            {
+                Modes.GetModes(new double[] { frequency }, new double[] { power }, new double[] { decayTime }, RESONANCE, modes, modeLengths);
+            }
+            finally
+            {
+                Modes.dampingSlope = dampingSlope;
+            }
+            Assert.AreEqual(longestModeCount, modeLengths[0]);
+            // Calculate the envelope of each sample directly.
+            double pow = Math.Pow(10, power / 20);
+            double dcy = -60 / (decayTime * RESONANCE / 1e3) / 20;
+            double q = 2 * frequency * Math.PI;
+            double maxDeviation = 0;
+            for (int i = 0; i < modeLengths[0]; i++)
+            {
+                double t = i / Globals.framerateD;
+                double expected = Math.Cos(t * q) * pow * Math.Pow(10, t * dcy);
+                maxDeviation = Math.Max(maxDeviation, Math.Abs(modes[0][i] - expected));
+            }
+            TestContext.WriteLine("Samples: " + modeLengths[0] + ", maximum deviation relative to the onset amplitude: " + maxDeviation / pow);
+            Assert.Less(maxDeviation, 1e-9 * pow);
+        }
+    }
+}
@@ -0,0 +1,146 @@
+﻿using Clatter.Core;
+using NUnit.Framework;
+
+
+namespace Clatter.Tests
+{
+    [TestFixture]
+    public class ScrapeTests
+    {
+        private const double SPEED = 1;
+
+
+        [TestCase(false)]
+        [TestCase(true)]
+        public void SurfaceEnd(bool circularSurface)
+        {
+            // The number of surface points per block at SPEED. This is the same as Scrape's calculation if the samples length is the default.
+            int numPts = (int)(Math.Floor((SPEED / 10) / ScrapeMaterialData.SCRAPE_M_PER_PIXEL) + 1);
+            // Generate a surface whose d2sdx2 array is exactly 3 blocks long, so that the final index of the third block is the end of the surface.
+            const int numBlocks = 3;
+            ScrapeMaterialData scrapeMaterialData = ScrapeMaterialData.Generate(numPts * numBlocks + 2, 3e-6, new Random(0));
+            Assert.AreEqual(numPts * numBlocks, scrapeMaterialData.d2sdx2.Length);
+            bool previousCircularSurface = Scrape.circularSurface;
+            Scrape.circularSurface = circularSurface;
+            try
+            {
+                Scrape scrape = GetScrape(scrapeMaterialData, 0);
+                // Continue past the end of the surface.
+                for (int i = 0; i < numBlocks * 2; i++)
+                {
+                    Assert.IsTrue(scrape.GetAudio(SPEED), "Block: " + i);
+                    for (int j = 0; j < scrape.samples.length; j++)
+                    {
+                        Assert.IsTrue(scrape.samples.samples[j].IsFinite(), "Block: " + i + ", sample: " + j);
+                    }
+                }
+            }
+            finally
+            {
+                Scrape.circularSurface = previousCircularSurface;
+            }
+        }
+
+
+        [Test]
+        public void ThreadedMatchesSerial()
+        {
+            const int count = 16;
+            ScrapeMaterialData.Load(ScrapeMaterial.ceramic);
+            ScrapeMaterialData scrapeMaterialData = ScrapeMaterialData.Get(ScrapeMaterial.ceramic);
+            // Each scrape has a different seed and speed so that each scrape has distinct state.
+            Scrape[] serialScrapes = new Scrape[count];
+            Scrape[] threadedScrapes = new Scrape[count];
+            double[] speeds = new double[count];
+            for (int i = 0; i < count; i++)
+            {
+                serialScrapes[i] = GetScrape(scrapeMaterialData, i);
+                threadedScrapes[i] = GetScrape(scrapeMaterialData, i);
+                speeds[i] = 0.5 + i * 0.1;
+            }
+            bool[] serialGenerated = new bool[count];
+            bool[] threadedGenerated = new bool[count];
+            // Generate several blocks so that the continuation state (surface index, median filter, etc.) is tested too.
+            for (int block = 0; block < 5; block++)
+            {
+                Scrape.GetAudio(serialScrapes, speeds, count, ref serialGenerated, 1);
+                Scrape.GetAudio(threadedScrapes, speeds, count, ref threadedGenerated, 4);
+                for (int i = 0; i < count; i++)
+                {
+                    Assert.IsTrue(serialGenerated[i], "Block: " + block + ", scrape: " + i);
+                    Assert.AreEqual(serialGenerated[i], threadedGenerated[i], "Block: " + block + ", scrape: " + i);
+                    Assert.AreEqual(serialScrapes[i].samples.length, threadedScrapes[i].samples.length);
+                    for (int j = 0; j < serialScrapes[i].samples.length; j++)
+                    {
+                        Assert.AreEqual(serialScrapes[i].samples.samples[j], threadedScrapes[i].samples.samples[j], "Block: " + block + ", scrape: " + i + ", sample: " + j);
+                    }
+                }
+            }
+        }
+
+
+        [Test]
+        public void SaturationOversamplingReducesAliasing()
+        {
+            // Use a high saturation so that the saturation curve adds a lot of high frequencies. Bypass the median filter so that only the saturation curve is measured.
+            double previousSaturation = Scrape.saturation;
+            int previousSaturationOversampling = Scrape.saturationOversampling;
+            bool previousBypassMedianFilter = Scrape.bypassMedianFilter;
+            Scrape.saturation = 10;
+            Scrape.bypassMedianFilter = true;
+            try
+            {
+                double energy = GetHighFrequencyForceEnergy(1);
+                double oversampledEnergy = GetHighFrequencyForceEnergy(4);
+                TestContext.WriteLine("Energy above half of the Nyquist frequency: " + energy + " (no oversampling), " + oversampledEnergy + " (4x oversampling)");
+                Assert.Less(oversampledEnergy, energy);
+            }
+            finally
+            {
+                Scrape.saturation = previousSaturation;
+                Scrape.saturationOversampling = previousSaturationOversampling;
+                Scrape.bypassMedianFilter = previousBypassMedianFilter;
+            }
+        }
+
+
+        private static double GetHighFrequencyForceEnergy(int saturationOversampling)
+        {
+            Scrape.saturationOversampling = saturationOversampling;
+            ScrapeMaterialData.Load(ScrapeMaterial.ceramic);
+            Scrape scrape = GetScrape(ScrapeMaterialData.Get(ScrapeMaterial.ceramic), 0);
+            double[] force = new double[0];
+            double energy = 0;
+            for (int i = 0; i < 10; i++)
+            {
+                int length = scrape.GetForce(SPEED, ref force);
+                Assert.Greater(length, 0);
+                // Apply a Hann window and get the spectrum of the force.
+                int n = Fft.GetSize(length);
+                double[] real = new double[n];
+                double[] imaginary = new double[n];
+                for (int j = 0; j < length; j++)
+                {
+                    real[j] = force[j] * 0.5 * (1 - Math.Cos(2 * Math.PI * j / (length - 1)));
+                }
+                Fft.Transform(real, imaginary, n, false);
+                // Sum the energy above half of the Nyquist frequency.
+                for (int j = n / 4; j <= n / 2; j++)
+                {
+                    energy += real[j] * real[j] + imaginary[j] * imaginary[j];
+                }
+            }
+            return energy;
+        }
+
+
+        private static Scrape GetScrape(ScrapeMaterialData scrapeMaterialData, int seed)
+        {
+            ImpactMaterialData.Load(ImpactMaterial.glass_1);
+            ImpactMaterialData.Load(ImpactMaterial.stone_4);
+            ClatterObjectData primary = new ClatterObjectData(0, ImpactMaterial.glass_1, 0.2, 0.2, 1);
+            ClatterObjectData secondary = new ClatterObjectData(1, ImpactMaterial.stone_4, 0.5, 0.1, 100);
+            return new Scrape(scrapeMaterialData, primary, secondary, new Random(seed));
+        }
+    }
+}
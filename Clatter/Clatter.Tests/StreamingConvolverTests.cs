@@ -0,0 +1,44 @@
+﻿using Clatter.Core;
+using NUnit.Framework;
+
+
+namespace Clatter.Tests
+{
+    [TestFixture]
+    public class StreamingConvolverTests
+    {
+        [TestCase(4410, 9000)]
+        [TestCase(4410, 100)]
+        public void BlocksMatchFullConvolution(int blockLength, int kernelLength)
+        {
+            const int numBlocks = 4;
+            Random rng = new Random(0);
+            double[] input = new double[blockLength * numBlocks];
+            for (int i = 0; i < input.Length; i++)
+            {
+                input[i] = rng.NextDouble() * 2 - 1;
+            }
+            double[] kernel = new double[kernelLength];
+            for (int i = 0; i < kernel.Length; i++)
+            {
+                kernel[i] = rng.NextDouble() * 2 - 1;
+            }
+            // Convolve the whole input at once.
+            double[] expected = new double[0];
+            input.ConvolveFull(input.Length, kernel, kernel.Length, ref expected);
+            // Convolve the input one block at a time.
+            StreamingConvolver streamingConvolver = new StreamingConvolver(kernel, kernel.Length);
+            double[] block = new double[blockLength];
+            double[] output = new double[blockLength];
+            for (int i = 0; i < numBlocks; i++)
+            {
+                Buffer.BlockCopy(input, i * blockLength * 8, block, 0, blockLength * 8);
+                streamingConvolver.ProcessBlock(block, blockLength, ref output);
+                for (int j = 0; j < blockLength; j++)
+                {
+                    Assert.AreEqual(expected[i * blockLength + j], output[j], 1e-9, "Block: " + i + ", index: " + j);
+                }
+            }
+        }
+    }
+}
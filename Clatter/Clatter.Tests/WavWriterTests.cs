@@ -0,0 +1,119 @@
+﻿using Clatter.Core;
+using NUnit.Framework;
+
+
+namespace Clatter.Tests
+{
+    [TestFixture]
+    public class WavWriterTests
+    {
+        private const int NUM_FRAMES = 100;
+        // The SubFormat GUID of a WAVE_FORMAT_EXTENSIBLE header, excluding the format code.
+        private static readonly byte[] SubFormatGuid = { 0x00, 0x00, 0x00, 0x00, 0x10, 0x00, 0x80, 0x00, 0x00, 0xAA, 0x00, 0x38, 0x9B, 0x71 };
+
+
+        [Test]
+        public void ExtensibleFloat32Header()
+        {
+            const int channels = 6;
+            // Each frame has the same value in every channel.
+            byte[] audio = new byte[NUM_FRAMES * channels * 4];
+            for (int i = 0; i < NUM_FRAMES; i++)
+            {
+                for (int j = 0; j < channels; j++)
+                {
+                    Buffer.BlockCopy(BitConverter.GetBytes(GetValue(i)), 0, audio, (i * channels + j) * 4, 4);
+                }
+            }
+            byte[] raw = WriteFile("extensible.wav", audio, channels, true);
+            // The extensible header is 68 bytes long.
+            Assert.AreEqual(68 + audio.Length, raw.Length);
+            AssertRiffHeader(raw, 40, 0xFFFE, channels, 32);
+            // cbSize, the valid bits per sample, the default 5.1 channel mask, and the SubFormat.
+            Assert.AreEqual(22, BitConverter.ToUInt16(raw, 36));
+            Assert.AreEqual(32, BitConverter.ToUInt16(raw, 38));
+            Assert.AreEqual(0x3F, BitConverter.ToUInt32(raw, 40));
+            Assert.AreEqual(3, BitConverter.ToUInt16(raw, 44));
+            for (int i = 0; i < SubFormatGuid.Length; i++)
+            {
+                Assert.AreEqual(SubFormatGuid[i], raw[46 + i], "SubFormat index: " + (i + 2));
+            }
+            Assert.AreEqual("data", System.Text.Encoding.ASCII.GetString(raw, 60, 4));
+            Assert.AreEqual(audio.Length, BitConverter.ToInt32(raw, 64));
+            AssertReadBack("extensible.wav", 1e-7);
+        }
+
+
+        [Test]
+        public void MonoInt16Header()
+        {
+            byte[] audio = new byte[NUM_FRAMES * 2];
+            for (int i = 0; i < NUM_FRAMES; i++)
+            {
+                Buffer.BlockCopy(BitConverter.GetBytes(((double)GetValue(i)).ToInt16()), 0, audio, i * 2, 2);
+            }
+            byte[] raw = WriteFile("mono.wav", audio, 1, false);
+            // The PCM header is 44 bytes long.
+            Assert.AreEqual(44 + audio.Length, raw.Length);
+            AssertRiffHeader(raw, 16, 1, 1, 16);
+            Assert.AreEqual("data", System.Text.Encoding.ASCII.GetString(raw, 36, 4));
+            Assert.AreEqual(audio.Length, BitConverter.ToInt32(raw, 40));
+            AssertReadBack("mono.wav", 1.0 / 32767);
+        }
+
+
+        private static float GetValue(int frame)
+        {
+            return (float)Math.Sin(frame * 0.1) * 0.5f;
+        }
+
+
+        private static string GetPath(string filename)
+        {
+            return Path.Combine(Path.GetTempPath(), "clatter_tests", filename);
+        }
+
+
+        private static byte[] WriteFile(string filename, byte[] audio, int channels, bool floatingPoint)
+        {
+            string path = GetPath(filename);
+            WavWriter writer = new WavWriter(path, true, channels, floatingPoint);
+            writer.Write(audio);
+            writer.End();
+            return File.ReadAllBytes(path);
+        }
+
+
+        private static void AssertRiffHeader(byte[] raw, int formatChunkSize, int formatTag, int channels, int bitsPerSample)
+        {
+            int bytesPerSample = bitsPerSample / 8;
+            Assert.AreEqual("RIFF", System.Text.Encoding.ASCII.GetString(raw, 0, 4));
+            // The RIFF chunk size excludes the "RIFF" ID and the size itself.
+            Assert.AreEqual(raw.Length - 8, BitConverter.ToInt32(raw, 4));
+            Assert.AreEqual("WAVE", System.Text.Encoding.ASCII.GetString(raw, 8, 4));
+            Assert.AreEqual("fmt ", System.Text.Encoding.ASCII.GetString(raw, 12, 4));
+            Assert.AreEqual(formatChunkSize, BitConverter.ToInt32(raw, 16));
+            Assert.AreEqual(formatTag, BitConverter.ToUInt16(raw, 20));
+            Assert.AreEqual(channels, BitConverter.ToUInt16(raw, 22));
+            Assert.AreEqual(Globals.framerate, BitConverter.ToInt32(raw, 24));
+            Assert.AreEqual(Globals.framerate * channels * bytesPerSample, BitConverter.ToInt32(raw, 28));
+            Assert.AreEqual(channels * bytesPerSample, BitConverter.ToUInt16(raw, 32));
+            Assert.AreEqual(bitsPerSample, BitConverter.ToUInt16(raw, 34));
+        }
+
+
+        private static void AssertReadBack(string filename, double delta)
+        {
+            double[] samples = new double[0];
+            int length;
+            int framerate;
+            Assert.IsTrue(WavReader.TryReadMono(GetPath(filename), ref samples, out length, out framerate));
+            Assert.AreEqual(NUM_FRAMES, length);
+            Assert.AreEqual(Globals.framerate, framerate);
+            for (int i = 0; i < NUM_FRAMES; i++)
+            {
+                Assert.AreEqual(GetValue(i), samples[i], delta, "Frame: " + i);
+            }
+        }
+    }
+}